@@ -0,0 +1,106 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackerError {
+    EmptyName,
+    NegativeAmount,
+    LeftoverExceedsAmount,
+    FussinessOutOfRange(u8),
+    NonPositiveWeight,
+    InvalidTimestamp(String),
+    InvalidDisplayFormat(String),
+    /// The unrecognised input, plus the closest known type name when one is near.
+    UnknownFeedingType(String, Option<&'static str>),
+    UnknownDejectionType(String),
+    InvalidData(String),
+    MissingField(&'static str),
+    DuplicateFeeding,
+    NotFound(u64),
+    IdsExhausted,
+}
+
+impl fmt::Display for TrackerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrackerError::EmptyName => write!(f, "Baby name cannot be empty"),
+            TrackerError::NegativeAmount => write!(f, "Amount cannot be negative"),
+            TrackerError::LeftoverExceedsAmount => write!(f, "Leftover cannot exceed the amount offered"),
+            TrackerError::FussinessOutOfRange(level) => {
+                write!(f, "Fussiness must be between 0 and {}, got {}", crate::models::MAX_FUSSINESS, level)
+            }
+            TrackerError::NonPositiveWeight => write!(f, "Weight must be positive"),
+            TrackerError::InvalidTimestamp(s) => {
+                write!(f, "Invalid timestamp: '{}'. Use YYYY-MM-DDTHH:MM:SS", s)
+            }
+            TrackerError::InvalidDisplayFormat(s) => {
+                write!(f, "Invalid display format: '{}'. Use strftime specifiers such as %d/%m/%Y %H:%M", s)
+            }
+            TrackerError::UnknownFeedingType(s, suggestion) => {
+                write!(f, "Unknown feeding type: '{}'. ", s)?;
+                if let Some(name) = suggestion {
+                    write!(f, "Did you mean '{}'? ", name)?;
+                }
+                write!(f, "Use: breast-left (bl), breast-right (br), bottle (b), solid (s)")
+            }
+            TrackerError::UnknownDejectionType(s) => write!(
+                f,
+                "Unknown dejection type: '{}'. Use: urine (pee/u), poop (p)",
+                s
+            ),
+            TrackerError::InvalidData(msg) => write!(f, "Invalid data: {}", msg),
+            TrackerError::MissingField(field) => write!(f, "Missing required field: {}", field),
+            TrackerError::DuplicateFeeding => {
+                write!(f, "A matching feeding was already logged at almost the same time")
+            }
+            TrackerError::NotFound(id) => write!(f, "No entry with id {}", id),
+            TrackerError::IdsExhausted => write!(f, "No ids left to assign"),
+        }
+    }
+}
+
+impl std::error::Error for TrackerError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_preserves_messages() {
+        assert_eq!(TrackerError::EmptyName.to_string(), "Baby name cannot be empty");
+        assert_eq!(TrackerError::NegativeAmount.to_string(), "Amount cannot be negative");
+        assert_eq!(
+            TrackerError::LeftoverExceedsAmount.to_string(),
+            "Leftover cannot exceed the amount offered"
+        );
+        assert_eq!(
+            TrackerError::FussinessOutOfRange(9).to_string(),
+            "Fussiness must be between 0 and 5, got 9"
+        );
+        assert_eq!(TrackerError::NonPositiveWeight.to_string(), "Weight must be positive");
+        assert_eq!(
+            TrackerError::InvalidTimestamp("bad".to_string()).to_string(),
+            "Invalid timestamp: 'bad'. Use YYYY-MM-DDTHH:MM:SS"
+        );
+        assert!(TrackerError::InvalidDisplayFormat("%Q".to_string())
+            .to_string()
+            .starts_with("Invalid display format: '%Q'"));
+        assert_eq!(
+            TrackerError::UnknownFeedingType("juice".to_string(), None).to_string(),
+            "Unknown feeding type: 'juice'. Use: breast-left (bl), breast-right (br), bottle (b), solid (s)"
+        );
+        assert!(TrackerError::UnknownFeedingType("botle".to_string(), Some("bottle"))
+            .to_string()
+            .contains("Did you mean 'bottle'?"));
+        assert!(TrackerError::UnknownDejectionType("vomit".to_string())
+            .to_string()
+            .starts_with("Unknown dejection type: 'vomit'"));
+        assert_eq!(TrackerError::NotFound(7).to_string(), "No entry with id 7");
+        assert_eq!(TrackerError::IdsExhausted.to_string(), "No ids left to assign");
+    }
+
+    #[test]
+    fn is_std_error() {
+        let e: Box<dyn std::error::Error> = Box::new(TrackerError::EmptyName);
+        assert_eq!(e.to_string(), "Baby name cannot be empty");
+    }
+}
@@ -1,3 +1,4 @@
+pub mod error;
 pub mod models;
 pub mod store;
 pub mod tracker;
@@ -11,6 +12,12 @@ pub struct BabyTracker {
     inner: Tracker,
 }
 
+impl Default for BabyTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 impl BabyTracker {
     #[wasm_bindgen(constructor)]
@@ -22,49 +29,291 @@ impl BabyTracker {
 
     #[wasm_bindgen(js_name = loadData)]
     pub fn load_data(json: &str) -> Result<BabyTracker, JsError> {
-        let inner = Tracker::from_json(json).map_err(|e| JsError::new(&e))?;
+        let inner = Tracker::from_json(json).map_err(JsError::from)?;
+        Ok(BabyTracker { inner })
+    }
+
+    /// Only accepts data from `exportBinary` of the same format version.
+    #[wasm_bindgen(js_name = loadBinary)]
+    pub fn load_binary(data: &[u8]) -> Result<BabyTracker, JsError> {
+        let inner = Tracker::from_bincode(data).map_err(JsError::from)?;
         Ok(BabyTracker { inner })
     }
 
+    #[wasm_bindgen(js_name = loadNdjson)]
+    pub fn load_ndjson(data: &str) -> Result<BabyTracker, JsError> {
+        let inner = Tracker::from_ndjson(data).map_err(JsError::from)?;
+        Ok(BabyTracker { inner })
+    }
+
+    /// JSON Schema (draft-07) describing the `exportData` format.
+    #[wasm_bindgen(js_name = jsonSchema)]
+    pub fn json_schema() -> String {
+        Tracker::json_schema()
+    }
+
     #[wasm_bindgen(js_name = exportData)]
     pub fn export_data(&self) -> String {
         self.inner.export_data()
     }
 
+    /// Feedings, dejections and weights combined.
+    #[wasm_bindgen(js_name = entryCount)]
+    pub fn entry_count(&self) -> usize {
+        self.inner.entry_count()
+    }
+
+    /// Set after `loadData` when the export's entry count or content hash did
+    /// not match (truncated or edited file); the data is still loaded.
+    #[wasm_bindgen(js_name = loadWarning)]
+    pub fn load_warning(&self) -> Option<String> {
+        self.inner.load_warning()
+    }
+
+    /// True when data or settings changed since the last `exportData` or
+    /// `exportBinary`, so autosave can skip unchanged writes.
+    #[wasm_bindgen(js_name = isDirty)]
+    pub fn is_dirty(&self) -> bool {
+        self.inner.is_dirty()
+    }
+
+    /// `exportData` restricted to one baby; loads back like any other export.
+    #[wasm_bindgen(js_name = exportBabyData)]
+    pub fn export_baby_data(&self, baby_name: &str) -> String {
+        self.inner.export_baby_data(baby_name)
+    }
+
+    #[wasm_bindgen(js_name = exportBinary)]
+    pub fn export_binary(&self) -> Vec<u8> {
+        self.inner.export_bincode()
+    }
+
+    #[wasm_bindgen(js_name = exportNdjson)]
+    pub fn export_ndjson(&self) -> String {
+        self.inner.export_ndjson()
+    }
+
+    /// Returns the number of records added from `json`.
+    #[wasm_bindgen(js_name = mergeFrom)]
+    pub fn merge_from(&mut self, json: &str) -> Result<usize, JsError> {
+        self.inner.merge_from(json).map_err(JsError::from)
+    }
+
+    /// Entries found only here (`only_in_self`) or only in `json` (`only_in_other`),
+    /// matched by content rather than id.
+    #[wasm_bindgen(js_name = diffWith)]
+    pub fn diff_with(&self, json: &str) -> Result<String, JsError> {
+        self.inner.diff_with(json).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = deleteRange)]
+    pub fn delete_range(&mut self, baby_name: Option<String>, start: &str, end: &str) -> Result<usize, JsError> {
+        self.inner
+            .delete_range(baby_name.as_deref(), start, end)
+            .map_err(JsError::from)
+    }
+
+    /// Removes every entry before `cutoff`; weights survive when `keepWeights` is set.
+    #[wasm_bindgen(js_name = pruneOlderThan)]
+    pub fn prune_older_than(&mut self, cutoff: &str, keep_weights: bool) -> Result<usize, JsError> {
+        self.inner
+            .prune_older_than(cutoff, keep_weights)
+            .map_err(JsError::from)
+    }
+
+    /// `kind` is `"feeding"`, `"dejection"` or `"weight"`; anything else removes nothing.
+    #[wasm_bindgen(js_name = deleteByKind)]
+    pub fn delete_by_kind(&mut self, kind: &str, baby_name: Option<String>) -> usize {
+        self.inner.delete_by_kind(kind, baby_name.as_deref())
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear()
+    }
+
+    pub fn reindex(&mut self) {
+        self.inner.reindex()
+    }
+
+    /// Rounds feeding amounts to the nearest `step` ml on add/update; 0 disables.
+    #[wasm_bindgen(js_name = setVolumeRounding)]
+    pub fn set_volume_rounding(&mut self, step: f64) {
+        self.inner.set_volume_rounding(step)
+    }
+
+    /// `"exact"` (default) or `"case-insensitive"` matching of baby names.
+    #[wasm_bindgen(js_name = setNameMatching)]
+    pub fn set_name_matching(&mut self, mode: &str) -> Result<(), JsError> {
+        self.inner.set_name_matching(mode).map_err(JsError::from)
+    }
+
     // --- Feeding ---
 
+    /// For a relative `timestamp` (`"now-30m"` with a `now`) or to reject a
+    /// near-identical feeding logged within a minute (`"dedupe": true`), use
+    /// `addEntry`.
     #[wasm_bindgen(js_name = addFeeding)]
+    #[allow(clippy::too_many_arguments)]
     pub fn add_feeding(
         &mut self,
         baby_name: &str,
         feeding_type: &str,
         amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
         duration_minutes: Option<u32>,
         notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
         timestamp: &str,
-    ) -> Result<u32, JsError> {
+    ) -> Result<u64, JsError> {
         self.inner
-            .add_feeding(baby_name, feeding_type, amount_ml, duration_minutes, notes, timestamp)
-            .map_err(|e| JsError::new(&e))
+            .add_feeding(
+                baby_name,
+                feeding_type,
+                amount_ml,
+                leftover_ml,
+                duration_minutes,
+                notes,
+                source,
+                fussiness,
+                timestamp,
+            )
+            .map_err(JsError::from)
+    }
+
+    /// Logs the same feeding for each name in a comma-separated list (tandem
+    /// feeding), returning the new ids; names are trimmed and empty segments
+    /// skipped. Nothing is added if any name is invalid.
+    #[wasm_bindgen(js_name = addFeedingMulti)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_feeding_multi(
+        &mut self,
+        names: &str,
+        feeding_type: &str,
+        amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
+        duration_minutes: Option<u32>,
+        notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
+        timestamp: &str,
+    ) -> Result<Vec<u64>, JsError> {
+        let names = tracker::split_names(names);
+        self.inner
+            .add_feeding_multi(
+                names,
+                feeding_type,
+                amount_ml,
+                leftover_ml,
+                duration_minutes,
+                notes,
+                source,
+                fussiness,
+                timestamp,
+            )
+            .map_err(JsError::from)
+    }
+
+    /// `now` is supplied by the caller, usually the client's current time.
+    #[wasm_bindgen(js_name = addQuickFeeding)]
+    pub fn add_quick_feeding(&mut self, baby_name: &str, feeding_type: &str, now: &str) -> Result<u64, JsError> {
+        self.inner
+            .add_quick_feeding(baby_name, feeding_type, now)
+            .map_err(JsError::from)
     }
 
     #[wasm_bindgen(js_name = updateFeeding)]
+    #[allow(clippy::too_many_arguments)]
     pub fn update_feeding(
         &mut self,
-        id: u32,
+        id: u64,
         feeding_type: &str,
         amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
         duration_minutes: Option<u32>,
         notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
         timestamp: &str,
     ) -> Result<bool, JsError> {
         self.inner
-            .update_feeding(id, feeding_type, amount_ml, duration_minutes, notes, timestamp)
-            .map_err(|e| JsError::new(&e))
+            .update_feeding(
+                id,
+                feeding_type,
+                amount_ml,
+                leftover_ml,
+                duration_minutes,
+                notes,
+                source,
+                fussiness,
+                timestamp,
+            )
+            .map_err(JsError::from)
+    }
+
+    /// `updateFeeding` that returns undefined when the id is unknown, otherwise
+    /// whether any stored value changed.
+    #[wasm_bindgen(js_name = updateFeedingReporting)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_feeding_reporting(
+        &mut self,
+        id: u64,
+        feeding_type: &str,
+        amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
+        duration_minutes: Option<u32>,
+        notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
+        timestamp: &str,
+    ) -> Result<Option<bool>, JsError> {
+        self.inner
+            .update_feeding_reporting(
+                id,
+                feeding_type,
+                amount_ml,
+                leftover_ml,
+                duration_minutes,
+                notes,
+                source,
+                fussiness,
+                timestamp,
+            )
+            .map_err(JsError::from)
+    }
+
+    /// Every argument after `id` is optional; omitted ones keep their value.
+    #[wasm_bindgen(js_name = patchFeeding)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn patch_feeding(
+        &mut self,
+        id: u64,
+        feeding_type: Option<String>,
+        amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
+        duration_minutes: Option<u32>,
+        notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
+        timestamp: Option<String>,
+    ) -> Result<bool, JsError> {
+        self.inner
+            .patch_feeding(
+                id,
+                feeding_type.as_deref(),
+                amount_ml,
+                leftover_ml,
+                duration_minutes,
+                notes,
+                source,
+                fussiness,
+                timestamp.as_deref(),
+            )
+            .map_err(JsError::from)
     }
 
     #[wasm_bindgen(js_name = deleteFeeding)]
-    pub fn delete_feeding(&mut self, id: u32) -> bool {
+    pub fn delete_feeding(&mut self, id: u64) -> bool {
         self.inner.delete_feeding(id)
     }
 
@@ -75,29 +324,31 @@ impl BabyTracker {
         &mut self,
         baby_name: &str,
         dejection_type: &str,
+        rash: bool,
         notes: Option<String>,
         timestamp: &str,
-    ) -> Result<u32, JsError> {
+    ) -> Result<u64, JsError> {
         self.inner
-            .add_dejection(baby_name, dejection_type, notes, timestamp)
-            .map_err(|e| JsError::new(&e))
+            .add_dejection(baby_name, dejection_type, rash, notes, timestamp)
+            .map_err(JsError::from)
     }
 
     #[wasm_bindgen(js_name = updateDejection)]
     pub fn update_dejection(
         &mut self,
-        id: u32,
+        id: u64,
         dejection_type: &str,
+        rash: bool,
         notes: Option<String>,
         timestamp: &str,
     ) -> Result<bool, JsError> {
         self.inner
-            .update_dejection(id, dejection_type, notes, timestamp)
-            .map_err(|e| JsError::new(&e))
+            .update_dejection(id, dejection_type, rash, notes, timestamp)
+            .map_err(JsError::from)
     }
 
     #[wasm_bindgen(js_name = deleteDejection)]
-    pub fn delete_dejection(&mut self, id: u32) -> bool {
+    pub fn delete_dejection(&mut self, id: u64) -> bool {
         self.inner.delete_dejection(id)
     }
 
@@ -110,41 +361,241 @@ impl BabyTracker {
         weight_kg: f64,
         notes: Option<String>,
         timestamp: &str,
-    ) -> Result<u32, JsError> {
+    ) -> Result<u64, JsError> {
         self.inner
             .add_weight(baby_name, weight_kg, notes, timestamp)
-            .map_err(|e| JsError::new(&e))
+            .map_err(JsError::from)
     }
 
     #[wasm_bindgen(js_name = updateWeight)]
     pub fn update_weight(
         &mut self,
-        id: u32,
+        id: u64,
         weight_kg: f64,
         notes: Option<String>,
         timestamp: &str,
     ) -> Result<bool, JsError> {
         self.inner
             .update_weight(id, weight_kg, notes, timestamp)
-            .map_err(|e| JsError::new(&e))
+            .map_err(JsError::from)
+    }
+
+    /// Generic add for dynamic UIs: `kind` is `"feeding"`, `"dejection"` or
+    /// `"weight"`, `json` carries the same fields as the typed add (snake_case).
+    /// A feeding may also set `"dedupe": true`. Returns `{id, kind}`.
+    #[wasm_bindgen(js_name = addEntry)]
+    pub fn add_entry(&mut self, kind: &str, json: &str) -> Result<String, JsError> {
+        self.inner.add_entry(kind, json).map_err(JsError::from)
     }
 
     #[wasm_bindgen(js_name = deleteWeight)]
-    pub fn delete_weight(&mut self, id: u32) -> bool {
+    pub fn delete_weight(&mut self, id: u64) -> bool {
         self.inner.delete_weight(id)
     }
 
+    #[wasm_bindgen(js_name = latestOfEach)]
+    pub fn latest_of_each(&self, baby_name: Option<String>) -> String {
+        self.inner.latest_of_each(baby_name.as_deref())
+    }
+
+    /// The event of any kind just before `timestamp` as timeline JSON, or `null`.
+    #[wasm_bindgen(js_name = lastEntryBefore)]
+    pub fn last_entry_before(&self, baby_name: Option<String>, timestamp: &str) -> Result<String, JsError> {
+        self.inner
+            .last_entry_before(baby_name.as_deref(), timestamp)
+            .map_err(JsError::from)
+    }
+
+    /// Today's counts, last feeding, minutes since it, and latest weight as JSON.
+    #[wasm_bindgen(js_name = getGlance)]
+    pub fn get_glance(&self, baby_name: Option<String>, now: &str) -> Result<String, JsError> {
+        self.inner.glance(baby_name.as_deref(), now).map_err(JsError::from)
+    }
+
+    /// Minutes since the last diaper as a plain number, or undefined.
+    #[wasm_bindgen(js_name = minutesSinceLastDejection)]
+    pub fn minutes_since_last_dejection(&self, baby_name: Option<String>, now: &str) -> Result<Option<f64>, JsError> {
+        self.inner
+            .minutes_since_last_dejection(baby_name.as_deref(), now)
+            .map(|m| m.map(|m| m as f64))
+            .map_err(JsError::from)
+    }
+
+    /// Minutes since the last weigh-in as a plain number, or undefined.
+    #[wasm_bindgen(js_name = minutesSinceLastWeight)]
+    pub fn minutes_since_last_weight(&self, baby_name: Option<String>, now: &str) -> Result<Option<f64>, JsError> {
+        self.inner
+            .minutes_since_last_weight(baby_name.as_deref(), now)
+            .map(|m| m.map(|m| m as f64))
+            .map_err(JsError::from)
+    }
+
+    /// Day summary, time since the last feeding, predicted next feeding and
+    /// latest weight as one JSON object.
+    #[wasm_bindgen(js_name = getDashboard)]
+    pub fn get_dashboard(&self, baby_name: Option<String>, now: &str) -> Result<String, JsError> {
+        self.inner.dashboard(baby_name.as_deref(), now).map_err(JsError::from)
+    }
+
+    // --- Duplicate ---
+
+    #[wasm_bindgen(js_name = duplicateFeeding)]
+    pub fn duplicate_feeding(&mut self, id: u64, timestamp: Option<String>) -> Result<Option<u64>, JsError> {
+        self.inner
+            .duplicate_feeding(id, timestamp.as_deref())
+            .map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = duplicateDejection)]
+    pub fn duplicate_dejection(&mut self, id: u64, timestamp: Option<String>) -> Result<Option<u64>, JsError> {
+        self.inner
+            .duplicate_dejection(id, timestamp.as_deref())
+            .map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = duplicateWeight)]
+    pub fn duplicate_weight(&mut self, id: u64, timestamp: Option<String>) -> Result<Option<u64>, JsError> {
+        self.inner
+            .duplicate_weight(id, timestamp.as_deref())
+            .map_err(JsError::from)
+    }
+
+    /// Copies one day's feedings to another at the same times of day; returns
+    /// the new ids as a JSON array.
+    #[wasm_bindgen(js_name = cloneDay)]
+    pub fn clone_day(&mut self, baby_name: Option<String>, from_date: &str, to_date: &str) -> Result<String, JsError> {
+        self.inner
+            .clone_day(baby_name.as_deref(), from_date, to_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Data quality ---
+
+    #[wasm_bindgen(js_name = validateData)]
+    pub fn validate_data(&self, now: &str) -> Result<String, JsError> {
+        self.inner.validate_data(now).map_err(JsError::from)
+    }
+
+    /// `[{id, kind}]` for bottles without an amount and breast feedings without
+    /// a duration.
+    #[wasm_bindgen(js_name = incompleteFeedings)]
+    pub fn incomplete_feedings(&self, baby_name: Option<String>) -> String {
+        self.inner.incomplete_feedings(baby_name.as_deref())
+    }
+
+    // --- Lookup ---
+
+    #[wasm_bindgen(js_name = getEntry)]
+    pub fn get_entry(&self, id: u64) -> String {
+        self.inner.get_entry(id)
+    }
+
+    /// The stored feeding with every field as saved (`null` when unset), for
+    /// edit forms; `"null"` when the id is not a feeding.
+    #[wasm_bindgen(js_name = getFeedingRaw)]
+    pub fn get_feeding_raw(&self, id: u64) -> String {
+        self.inner.get_feeding_raw(id)
+    }
+
+    #[wasm_bindgen(js_name = getDejectionRaw)]
+    pub fn get_dejection_raw(&self, id: u64) -> String {
+        self.inner.get_dejection_raw(id)
+    }
+
+    #[wasm_bindgen(js_name = getWeightRaw)]
+    pub fn get_weight_raw(&self, id: u64) -> String {
+        self.inner.get_weight_raw(id)
+    }
+
+    /// Appends to an entry's notes (after `"; "`); false when the id is unknown.
+    #[wasm_bindgen(js_name = appendNote)]
+    pub fn append_note(&mut self, id: u64, text: &str) -> bool {
+        self.inner.append_note(id, text)
+    }
+
+    // --- Fussiness ---
+
+    /// Mean fussiness (0-5) over rated feedings in the range, or undefined if none.
+    #[wasm_bindgen(js_name = avgFussiness)]
+    pub fn avg_fussiness(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Option<f64>, JsError> {
+        self.inner
+            .avg_fussiness(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Counts by kind ---
+
+    /// JSON object of entry counts per kind; kinds with nothing logged are 0.
+    #[wasm_bindgen(js_name = countsByKind)]
+    pub fn counts_by_kind(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<String, JsError> {
+        self.inner
+            .counts_by_kind(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Tracking span ---
+
+    #[wasm_bindgen(js_name = trackingSpan)]
+    pub fn tracking_span(&self, baby_name: Option<String>) -> String {
+        self.inner.tracking_span(baby_name.as_deref())
+    }
+
+    #[wasm_bindgen(js_name = loggingStreak)]
+    pub fn logging_streak(&self, baby_name: Option<String>, date: &str) -> Result<u32, JsError> {
+        self.inner
+            .logging_streak(baby_name.as_deref(), date)
+            .map_err(JsError::from)
+    }
+
+    /// JSON array of `YYYY-MM-DD` days in `[start_date, end_date)` with nothing logged.
+    #[wasm_bindgen(js_name = missingDays)]
+    pub fn missing_days(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<String, JsError> {
+        self.inner
+            .missing_days(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
     // --- Timeline ---
 
+    /// `include_seconds` defaults to true; pass false for minute precision.
     #[wasm_bindgen(js_name = timelineForDay)]
     pub fn timeline_for_day(
         &self,
         baby_name: Option<String>,
         date: &str,
+        include_seconds: Option<bool>,
     ) -> Result<String, JsError> {
         self.inner
-            .timeline_for_day(baby_name.as_deref(), date)
-            .map_err(|e| JsError::new(&e))
+            .timeline_for_day(baby_name.as_deref(), date, include_seconds.unwrap_or(true))
+            .map_err(JsError::from)
+    }
+
+    /// Like `timelineForDay`, with a `cumulative_ml` running total on feedings.
+    #[wasm_bindgen(js_name = timelineForDayCumulative)]
+    pub fn timeline_for_day_cumulative(
+        &self,
+        baby_name: Option<String>,
+        date: &str,
+        include_seconds: Option<bool>,
+    ) -> Result<String, JsError> {
+        self.inner
+            .timeline_for_day_cumulative(baby_name.as_deref(), date, include_seconds.unwrap_or(true))
+            .map_err(JsError::from)
     }
 
     // --- Summary (day-bounded) ---
@@ -157,7 +608,140 @@ impl BabyTracker {
     ) -> Result<String, JsError> {
         self.inner
             .get_summary(baby_name.as_deref(), date)
-            .map_err(|e| JsError::new(&e))
+            .map_err(JsError::from)
+    }
+
+    /// Like `getSummary`, but every feeding type appears in `by_type`, zeros included.
+    #[wasm_bindgen(js_name = getSummaryFull)]
+    pub fn get_summary_full(&self, baby_name: Option<String>, date: &str) -> Result<String, JsError> {
+        self.inner
+            .get_summary_full(baby_name.as_deref(), date)
+            .map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = getSummaryPerBaby)]
+    pub fn get_summary_per_baby(&self, date: &str) -> Result<String, JsError> {
+        self.inner.get_summary_per_baby(date).map_err(JsError::from)
+    }
+
+    /// Language for `getSummaryText`, e.g. `"en"` or `"es"`. Unknown tags use English.
+    #[wasm_bindgen(js_name = setLocale)]
+    pub fn set_locale(&mut self, locale: &str) {
+        self.inner.set_locale(locale)
+    }
+
+    /// Accept unknown feeding type names (e.g. `"water"`) as custom types.
+    #[wasm_bindgen(js_name = setLenientFeedingTypes)]
+    pub fn set_lenient_feeding_types(&mut self, lenient: bool) {
+        self.inner.set_lenient_feeding_types(lenient)
+    }
+
+    /// `"metric"` (default) or `"imperial"` units for `getSummaryText`.
+    #[wasm_bindgen(js_name = setUnitSystem)]
+    pub fn set_unit_system(&mut self, units: &str) -> Result<(), JsError> {
+        self.inner.set_unit_system(units).map_err(JsError::from)
+    }
+
+    /// With `true`, every method taking a timestamp only accepts
+    /// `YYYY-MM-DDTHH:MM:SS`; shorter, zoned and relative (`now-30m`) forms are
+    /// rejected. Off by default and saved with the data.
+    #[wasm_bindgen(js_name = setStrictTimestamps)]
+    pub fn set_strict_timestamps(&mut self, strict: bool) {
+        self.inner.set_strict_timestamps(strict);
+    }
+
+    /// strftime pattern such as `"%d/%m/%Y %H:%M"` for `getSummaryText`,
+    /// `formatTimestamp` and `validate` messages; bad specifiers are rejected.
+    #[wasm_bindgen(js_name = setDisplayFormat)]
+    pub fn set_display_format(&mut self, fmt: &str) -> Result<(), JsError> {
+        self.inner.set_display_format(fmt).map_err(JsError::from)
+    }
+
+    /// Custom wording (e.g. emoji) for urine and poop in summaries and the
+    /// timeline's `label` field; an empty string keeps the default.
+    #[wasm_bindgen(js_name = setDejectionLabels)]
+    pub fn set_dejection_labels(&mut self, urine: &str, poop: &str) {
+        self.inner.set_dejection_labels(urine, poop)
+    }
+
+    #[wasm_bindgen(js_name = formatTimestamp)]
+    pub fn format_timestamp(&self, timestamp: &str) -> Result<String, JsError> {
+        self.inner.format_timestamp(timestamp).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = getSummaryText)]
+    pub fn get_summary_text(&self, baby_name: Option<String>, date: &str) -> Result<String, JsError> {
+        self.inner
+            .summary_text(baby_name.as_deref(), date)
+            .map_err(JsError::from)
+    }
+
+    // --- Daily goal ---
+
+    #[wasm_bindgen(js_name = setDailyGoalMl)]
+    pub fn set_daily_goal_ml(&mut self, goal_ml: f64) {
+        self.inner.set_daily_goal_ml(goal_ml)
+    }
+
+    /// JSON `{consumed_ml, goal_ml}` for the day, or `"null"` when no goal is set.
+    #[wasm_bindgen(js_name = goalProgress)]
+    pub fn goal_progress(&self, baby_name: Option<String>, date: &str) -> Result<String, JsError> {
+        self.inner
+            .goal_progress(baby_name.as_deref(), date)
+            .map_err(JsError::from)
+    }
+
+    // --- Intake per kg ---
+
+    /// The day's ml over the latest known weight, or undefined without a weight.
+    #[wasm_bindgen(js_name = mlPerKgPerDay)]
+    pub fn ml_per_kg_per_day(&self, baby_name: Option<String>, date: &str) -> Result<Option<f64>, JsError> {
+        self.inner
+            .ml_per_kg_per_day(baby_name.as_deref(), date)
+            .map_err(JsError::from)
+    }
+
+    // --- Baby metadata ---
+
+    /// `birthdate` is `YYYY-MM-DD`.
+    #[wasm_bindgen(js_name = setBabyMeta)]
+    pub fn set_baby_meta(
+        &mut self,
+        baby_name: &str,
+        color: Option<String>,
+        birthdate: Option<String>,
+    ) -> Result<(), JsError> {
+        self.inner
+            .set_baby_meta(baby_name, color, birthdate.as_deref())
+            .map_err(JsError::from)
+    }
+
+    /// JSON `{color, birthdate}`, or `"null"` when nothing is stored.
+    #[wasm_bindgen(js_name = getBabyMeta)]
+    pub fn get_baby_meta(&self, baby_name: &str) -> String {
+        self.inner.get_baby_meta(baby_name)
+    }
+
+    /// JSON `{weeks, days}`, or `"null"` without a birthdate or before birth.
+    #[wasm_bindgen(js_name = ageAt)]
+    pub fn age_at(&self, baby_name: &str, timestamp: &str) -> Result<String, JsError> {
+        self.inner.age_at(baby_name, timestamp).map_err(JsError::from)
+    }
+
+    // --- Sync ---
+
+    /// JSON array of every entry ascending by id, for incremental sync.
+    #[wasm_bindgen(js_name = entriesById)]
+    pub fn entries_by_id(&self) -> String {
+        self.inner.entries_by_id()
+    }
+
+    // --- Lifetime totals ---
+
+    /// All-time totals as JSON, with no date bounds.
+    #[wasm_bindgen(js_name = lifetimeTotals)]
+    pub fn lifetime_totals(&self, baby_name: Option<String>) -> String {
+        self.inner.lifetime_totals(baby_name.as_deref())
     }
 
     // --- Report ---
@@ -171,6 +755,187 @@ impl BabyTracker {
     ) -> Result<String, JsError> {
         self.inner
             .report(baby_name.as_deref(), start_date, end_date)
-            .map_err(|e| JsError::new(&e))
+            .map_err(JsError::from)
+    }
+
+    /// Per-day feed counts and ml split into morning (6-12), afternoon (12-18),
+    /// evening (18-22) and night (22-6).
+    #[wasm_bindgen(js_name = getDaypartReport)]
+    pub fn get_daypart_report(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<String, JsError> {
+        self.inner
+            .report_by_daypart(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Average daily volume ---
+
+    #[wasm_bindgen(js_name = avgDailyMl)]
+    pub fn avg_daily_ml(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<f64, JsError> {
+        self.inner
+            .avg_daily_ml(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Weight velocity ---
+
+    /// Grams per day between the first and last weigh-in, or undefined.
+    #[wasm_bindgen(js_name = weightVelocity)]
+    pub fn weight_velocity(&self, baby_name: Option<String>) -> Option<f64> {
+        self.inner.weight_velocity(baby_name.as_deref())
+    }
+
+    /// `[[date, kg], ...]` for a sparkline; one point per day with a weigh-in.
+    #[wasm_bindgen(js_name = weightSeries)]
+    pub fn weight_series(&self, baby_name: Option<String>, start_date: &str, end_date: &str) -> Result<String, JsError> {
+        self.inner
+            .weight_series(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Calorie estimate ---
+
+    #[wasm_bindgen(js_name = setCalorieFactors)]
+    pub fn set_calorie_factors(&mut self, kcal_per_ml: f64, kcal_per_solid: f64) {
+        self.inner.set_calorie_factors(kcal_per_ml, kcal_per_solid)
+    }
+
+    #[wasm_bindgen(js_name = estimatedCalories)]
+    pub fn estimated_calories(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<f64, JsError> {
+        self.inner
+            .estimated_calories(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Hourly histogram ---
+
+    #[wasm_bindgen(js_name = feedingHourHistogram)]
+    pub fn feeding_hour_histogram(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<String, JsError> {
+        self.inner
+            .feeding_hour_histogram(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    /// `[[hour, avgMl], ...]` for hours 0-23, averaged over the days in range.
+    #[wasm_bindgen(js_name = typicalDay)]
+    pub fn typical_day(&self, baby_name: Option<String>, start_date: &str, end_date: &str) -> Result<String, JsError> {
+        self.inner
+            .typical_day(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    /// `[[label, count], ...]` of breast feeding durations in 0-5, 5-10, 10-20
+    /// and 20+ minute buckets.
+    #[wasm_bindgen(js_name = nursingDurationBuckets)]
+    pub fn nursing_duration_buckets(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<String, JsError> {
+        self.inner
+            .nursing_duration_buckets(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Next feeding prediction ---
+
+    #[wasm_bindgen(js_name = predictNextFeeding)]
+    pub fn predict_next_feeding(&self, baby_name: Option<String>, now: &str) -> Result<Option<String>, JsError> {
+        self.inner
+            .predict_next_feeding(baby_name.as_deref(), now)
+            .map_err(JsError::from)
+    }
+
+    // --- Typical bottle size ---
+
+    /// Median of recent bottle amounts, or undefined with fewer than three.
+    #[wasm_bindgen(js_name = typicalBottleMl)]
+    pub fn typical_bottle_ml(&self, baby_name: Option<String>) -> Option<f64> {
+        self.inner.typical_bottle_ml(baby_name.as_deref())
+    }
+
+    // --- Milk sources ---
+
+    /// JSON array of the distinct feeding sources recorded, sorted.
+    #[wasm_bindgen(js_name = sourcesUsed)]
+    pub fn sources_used(&self, baby_name: Option<String>) -> String {
+        self.inner.sources_used(baby_name.as_deref())
+    }
+
+    // --- Cluster feeding ---
+
+    #[wasm_bindgen(js_name = clusterWindows)]
+    pub fn cluster_windows(&self, baby_name: Option<String>, min_count: usize, within_minutes: u32) -> String {
+        self.inner.cluster_windows(baby_name.as_deref(), min_count, within_minutes)
+    }
+
+    /// `[[start, minutes], ...]`: breast feedings starting within `gapMinutes`
+    /// of the previous side's end are summed into one session.
+    #[wasm_bindgen(js_name = mergedNursingSessions)]
+    pub fn merged_nursing_sessions(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+        gap_minutes: u32,
+    ) -> Result<String, JsError> {
+        self.inner
+            .merged_nursing_sessions(baby_name.as_deref(), start_date, end_date, gap_minutes)
+            .map_err(JsError::from)
+    }
+
+    // --- Diaper gaps ---
+
+    /// JSON array of minutes between consecutive diapers in the range.
+    #[wasm_bindgen(js_name = diaperGaps)]
+    pub fn diaper_gaps(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<String, JsError> {
+        self.inner
+            .diaper_gaps(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    // --- Breast balance ---
+
+    #[wasm_bindgen(js_name = breastBalance)]
+    pub fn breast_balance(
+        &self,
+        baby_name: Option<String>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<String, JsError> {
+        self.inner
+            .breast_balance(baby_name.as_deref(), start_date, end_date)
+            .map_err(JsError::from)
+    }
+
+    /// `"breast-left"`, `"breast-right"`, or null when there is no nursing yet.
+    #[wasm_bindgen(js_name = nextBreast)]
+    pub fn next_breast(&self, baby_name: Option<String>) -> Option<String> {
+        self.inner.next_breast(baby_name.as_deref())
     }
 }
@@ -1,7 +1,112 @@
 use std::fmt;
 
-use chrono::NaiveDateTime;
-use serde::{Deserialize, Serialize};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::TrackerError;
+
+// --- Locale ---
+
+/// Language for user-facing labels. Anything unrecognised falls back to English.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+impl Locale {
+    /// Matches on the language part of a tag, so `"es"`, `"es-MX"` and
+    /// `"es_ES"` are all Spanish.
+    pub fn parse(s: &str) -> Self {
+        let lang = s.split(['-', '_']).next().unwrap_or("").to_lowercase();
+        match lang.as_str() {
+            "es" => Locale::Es,
+            _ => Locale::En,
+        }
+    }
+}
+
+// --- UnitSystem ---
+
+/// Units used when rendering volumes and weights for display. Records are
+/// always stored in ml and kg.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+const ML_PER_FL_OZ: f64 = 29.5735;
+const OZ_PER_KG: f64 = 35.274;
+
+impl UnitSystem {
+    pub fn parse(s: &str) -> Result<Self, TrackerError> {
+        match s.to_lowercase().as_str() {
+            "metric" => Ok(UnitSystem::Metric),
+            "imperial" => Ok(UnitSystem::Imperial),
+            _ => Err(TrackerError::InvalidData(format!(
+                "unknown unit system '{}', use metric or imperial",
+                s
+            ))),
+        }
+    }
+
+    /// `"480 ml"` or `"16 oz"`, rounded to whole units.
+    pub fn format_volume(self, ml: f64) -> String {
+        match self {
+            UnitSystem::Metric => format!("{:.0} ml", ml),
+            UnitSystem::Imperial => format!("{:.0} oz", ml / ML_PER_FL_OZ),
+        }
+    }
+
+    /// `"3.6 kg"` or `"7 lb 15 oz"`, the latter rounded to the whole ounce.
+    pub fn format_weight(self, kg: f64) -> String {
+        match self {
+            UnitSystem::Metric => format!("{} kg", kg),
+            UnitSystem::Imperial => {
+                let total_oz = (kg * OZ_PER_KG).round() as u64;
+                format!("{} lb {} oz", total_oz / 16, total_oz % 16)
+            }
+        }
+    }
+}
+
+// --- NameMatching ---
+
+/// How a `baby_name` filter is compared against stored names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NameMatching {
+    #[default]
+    Exact,
+    /// "Emma" and "emma" are treated as the same baby.
+    CaseInsensitive,
+}
+
+impl NameMatching {
+    pub fn parse(s: &str) -> Result<Self, TrackerError> {
+        match s.to_lowercase().as_str() {
+            "exact" => Ok(NameMatching::Exact),
+            "case-insensitive" | "ci" => Ok(NameMatching::CaseInsensitive),
+            _ => Err(TrackerError::InvalidData(format!(
+                "unknown name matching '{}', use exact or case-insensitive",
+                s
+            ))),
+        }
+    }
+
+    /// True when there is no filter or `stored` matches it under this mode.
+    pub fn matches(self, filter: Option<&str>, stored: &str) -> bool {
+        filter.is_none_or(|name| match self {
+            NameMatching::Exact => stored == name,
+            NameMatching::CaseInsensitive => stored.to_lowercase() == name.to_lowercase(),
+        })
+    }
+}
 
 // --- FeedingType ---
 
@@ -12,62 +117,155 @@ pub enum FeedingType {
     BreastRight,
     Bottle,
     Solid,
+    /// A caller-named type such as "water", serialized as `{"other": "water"}`.
+    /// Only `parse_lenient` produces it from text.
+    Other(String),
 }
 
 impl fmt::Display for FeedingType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            FeedingType::BreastLeft => write!(f, "Breast (Left)"),
-            FeedingType::BreastRight => write!(f, "Breast (Right)"),
-            FeedingType::Bottle => write!(f, "Bottle"),
-            FeedingType::Solid => write!(f, "Solid"),
-        }
+        f.write_str(self.display_localized(Locale::En))
     }
 }
 
 impl FeedingType {
-    pub fn parse(s: &str) -> Result<Self, String> {
+    /// Every built-in type, in the order summaries list them. `Other` is not
+    /// included since its values are open-ended. A fixed-size array so per-type
+    /// counts can be destructured into `DayReport`'s fields.
+    pub fn all() -> &'static [FeedingType; 4] {
+        &[FeedingType::BreastLeft, FeedingType::BreastRight, FeedingType::Bottle, FeedingType::Solid]
+    }
+
+    /// Custom types show their own name in every locale.
+    pub fn display_localized(&self, locale: Locale) -> &str {
+        match (locale, self) {
+            (_, FeedingType::Other(name)) => name,
+            (Locale::En, FeedingType::BreastLeft) => "Breast (Left)",
+            (Locale::En, FeedingType::BreastRight) => "Breast (Right)",
+            (Locale::En, FeedingType::Bottle) => "Bottle",
+            (Locale::En, FeedingType::Solid) => "Solid",
+            (Locale::Es, FeedingType::BreastLeft) => "Pecho (izquierdo)",
+            (Locale::Es, FeedingType::BreastRight) => "Pecho (derecho)",
+            (Locale::Es, FeedingType::Bottle) => "Biberón",
+            (Locale::Es, FeedingType::Solid) => "Sólidos",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, TrackerError> {
         match s.to_lowercase().as_str() {
             "breast-left" | "bl" => Ok(FeedingType::BreastLeft),
             "breast-right" | "br" => Ok(FeedingType::BreastRight),
             "bottle" | "b" => Ok(FeedingType::Bottle),
             "solid" | "s" => Ok(FeedingType::Solid),
-            _ => Err(format!(
-                "Unknown feeding type: '{}'. Use: breast-left (bl), breast-right (br), bottle (b), solid (s)",
-                s
-            )),
+            _ => Err(TrackerError::UnknownFeedingType(s.to_string(), Self::suggest(s))),
+        }
+    }
+
+    /// Like `parse`, but any other non-blank input becomes `Other`, trimmed.
+    pub fn parse_lenient(s: &str) -> Result<Self, TrackerError> {
+        match Self::parse(s) {
+            Err(_) if !s.trim().is_empty() => Ok(FeedingType::Other(s.trim().to_string())),
+            parsed => parsed,
+        }
+    }
+
+    /// The kebab-case name used in timeline JSON, or the custom name for `Other`.
+    pub fn wire_name(&self) -> &str {
+        match self {
+            FeedingType::BreastLeft => "breast-left",
+            FeedingType::BreastRight => "breast-right",
+            FeedingType::Bottle => "bottle",
+            FeedingType::Solid => "solid",
+            FeedingType::Other(name) => name,
+        }
+    }
+
+    /// Closest full type name within two edits of the input, if any.
+    fn suggest(s: &str) -> Option<&'static str> {
+        const MAX_DISTANCE: usize = 2;
+        let input = s.to_lowercase();
+        Self::all()
+            .iter()
+            .map(|ft| (levenshtein(&input, ft.wire_name()), ft.wire_name()))
+            .filter(|(d, _)| *d <= MAX_DISTANCE)
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, name)| name)
+    }
+}
+
+/// Edit distance (insertions, deletions, substitutions) between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
         }
+        prev = curr;
     }
+    prev[b.len()]
 }
 
 // --- Feeding ---
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Top of the 0-based fussiness scale recorded on feedings.
+pub const MAX_FUSSINESS: u8 = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Feeding {
-    pub id: u32,
+    pub id: u64,
     pub baby_name: String,
     pub feeding_type: FeedingType,
+    /// Amount offered; for a bottle, what was poured.
     pub amount_ml: Option<f64>,
+    /// What was left over from `amount_ml`, i.e. offered but not taken.
+    #[serde(default)]
+    pub leftover_ml: Option<f64>,
     pub duration_minutes: Option<u32>,
     pub notes: Option<String>,
+    /// Free-text origin of the milk, e.g. "donor" or "pumped".
+    #[serde(default)]
+    pub source: Option<String>,
+    /// 0 (settled) to `MAX_FUSSINESS` (inconsolable).
+    #[serde(default)]
+    pub fussiness: Option<u8>,
     pub timestamp: NaiveDateTime,
 }
 
 impl Feeding {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         baby_name: String,
         feeding_type: FeedingType,
         amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
         duration_minutes: Option<u32>,
         notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
         timestamp: NaiveDateTime,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, TrackerError> {
         if baby_name.trim().is_empty() {
-            return Err("Baby name cannot be empty".to_string());
+            return Err(TrackerError::EmptyName);
         }
         if let Some(ml) = amount_ml {
             if ml < 0.0 {
-                return Err("Amount cannot be negative".to_string());
+                return Err(TrackerError::NegativeAmount);
+            }
+        }
+        if let Some(left) = leftover_ml {
+            if left < 0.0 {
+                return Err(TrackerError::NegativeAmount);
+            }
+            if left > amount_ml.unwrap_or(0.0) {
+                return Err(TrackerError::LeftoverExceedsAmount);
+            }
+        }
+        if let Some(level) = fussiness {
+            if level > MAX_FUSSINESS {
+                return Err(TrackerError::FussinessOutOfRange(level));
             }
         }
         Ok(Feeding {
@@ -75,13 +273,159 @@ impl Feeding {
             baby_name: baby_name.trim().to_string(),
             feeding_type,
             amount_ml,
+            leftover_ml,
             duration_minutes,
             notes: notes.filter(|n| !n.trim().is_empty()),
+            source: source.filter(|s| !s.trim().is_empty()),
+            fussiness,
             timestamp,
         })
     }
 }
 
+impl Feeding {
+    /// Soft checks for amount/duration combinations that are usually a typo:
+    /// a bottle without an amount, a breast feeding with an amount but no
+    /// duration, or a solid with a volume. Each entry is a short description
+    /// such as "a bottle with no amount". Never rejects the feeding.
+    pub fn consistency_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        match self.feeding_type {
+            FeedingType::Bottle if self.amount_ml.is_none() => {
+                warnings.push("a bottle with no amount".to_string());
+            }
+            FeedingType::BreastLeft | FeedingType::BreastRight
+                if self.amount_ml.is_some() && self.duration_minutes.is_none() =>
+            {
+                warnings.push("a breast feeding with an amount but no duration".to_string());
+            }
+            FeedingType::Solid if self.amount_ml.is_some() => {
+                warnings.push("a solid with a volume in ml".to_string());
+            }
+            _ => {}
+        }
+        warnings
+    }
+
+    /// What the baby actually took: `amount_ml` less any `leftover_ml`. Intake
+    /// totals use this rather than the amount offered.
+    pub fn consumed_ml(&self) -> Option<f64> {
+        consumed_ml(self.amount_ml, self.leftover_ml)
+    }
+
+    /// Whether the field a feeding of this type is expected to carry is
+    /// missing: the amount for a bottle, the duration for a breast feeding.
+    /// Solids and custom types never count as incomplete.
+    pub fn is_incomplete(&self) -> bool {
+        match self.feeding_type {
+            FeedingType::Bottle => self.amount_ml.is_none(),
+            FeedingType::BreastLeft | FeedingType::BreastRight => self.duration_minutes.is_none(),
+            FeedingType::Solid | FeedingType::Other(_) => false,
+        }
+    }
+}
+
+// --- FeedingBuilder ---
+
+/// Named-setter alternative to `Feeding::new`. `feeding_type` and `timestamp`
+/// are required; `build` runs the same validation as `new`.
+#[derive(Debug, Default)]
+pub struct FeedingBuilder {
+    baby_name: String,
+    feeding_type: Option<FeedingType>,
+    amount_ml: Option<f64>,
+    leftover_ml: Option<f64>,
+    duration_minutes: Option<u32>,
+    notes: Option<String>,
+    source: Option<String>,
+    fussiness: Option<u8>,
+    timestamp: Option<NaiveDateTime>,
+}
+
+impl FeedingBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn baby_name(mut self, baby_name: impl Into<String>) -> Self {
+        self.baby_name = baby_name.into();
+        self
+    }
+
+    pub fn feeding_type(mut self, feeding_type: FeedingType) -> Self {
+        self.feeding_type = Some(feeding_type);
+        self
+    }
+
+    pub fn amount_ml(mut self, amount_ml: f64) -> Self {
+        self.amount_ml = Some(amount_ml);
+        self
+    }
+
+    pub fn leftover_ml(mut self, leftover_ml: f64) -> Self {
+        self.leftover_ml = Some(leftover_ml);
+        self
+    }
+
+    pub fn duration_minutes(mut self, duration_minutes: u32) -> Self {
+        self.duration_minutes = Some(duration_minutes);
+        self
+    }
+
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn fussiness(mut self, fussiness: u8) -> Self {
+        self.fussiness = Some(fussiness);
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: NaiveDateTime) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn build(self) -> Result<Feeding, TrackerError> {
+        let feeding_type = self.feeding_type.ok_or(TrackerError::MissingField("feeding_type"))?;
+        let timestamp = self.timestamp.ok_or(TrackerError::MissingField("timestamp"))?;
+        Feeding::new(
+            self.baby_name,
+            feeding_type,
+            self.amount_ml,
+            self.leftover_ml,
+            self.duration_minutes,
+            self.notes,
+            self.source,
+            self.fussiness,
+            timestamp,
+        )
+    }
+}
+
+// --- FeedingPatch ---
+
+/// Partial update for a stored feeding: each `None` field is left unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedingPatch {
+    pub feeding_type: Option<FeedingType>,
+    pub amount_ml: Option<f64>,
+    pub leftover_ml: Option<f64>,
+    pub duration_minutes: Option<u32>,
+    /// An empty or blank string clears the existing notes.
+    pub notes: Option<String>,
+    /// An empty or blank string clears the existing source.
+    pub source: Option<String>,
+    pub fussiness: Option<u8>,
+    pub timestamp: Option<NaiveDateTime>,
+}
+
 // --- DejectionType ---
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -93,33 +437,43 @@ pub enum DejectionType {
 
 impl fmt::Display for DejectionType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            DejectionType::Urine => write!(f, "Urine"),
-            DejectionType::Poop => write!(f, "Poop"),
-        }
+        f.write_str(self.display_localized(Locale::En))
     }
 }
 
 impl DejectionType {
-    pub fn parse(s: &str) -> Result<Self, String> {
+    /// Every dejection type, in the order summaries list them.
+    pub fn all() -> &'static [DejectionType; 2] {
+        &[DejectionType::Urine, DejectionType::Poop]
+    }
+
+    pub fn display_localized(&self, locale: Locale) -> &'static str {
+        match (locale, self) {
+            (Locale::En, DejectionType::Urine) => "Urine",
+            (Locale::En, DejectionType::Poop) => "Poop",
+            (Locale::Es, DejectionType::Urine) => "Orina",
+            (Locale::Es, DejectionType::Poop) => "Caca",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, TrackerError> {
         match s.to_lowercase().as_str() {
             "urine" | "pee" | "u" => Ok(DejectionType::Urine),
             "poop" | "p" => Ok(DejectionType::Poop),
-            _ => Err(format!(
-                "Unknown dejection type: '{}'. Use: urine (pee/u), poop (p)",
-                s
-            )),
+            _ => Err(TrackerError::UnknownDejectionType(s.to_string())),
         }
     }
 }
 
 // --- Dejection ---
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Dejection {
-    pub id: u32,
+    pub id: u64,
     pub baby_name: String,
     pub dejection_type: DejectionType,
+    #[serde(default)]
+    pub rash: bool,
     pub notes: Option<String>,
     pub timestamp: NaiveDateTime,
 }
@@ -128,16 +482,18 @@ impl Dejection {
     pub fn new(
         baby_name: String,
         dejection_type: DejectionType,
+        rash: bool,
         notes: Option<String>,
         timestamp: NaiveDateTime,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, TrackerError> {
         if baby_name.trim().is_empty() {
-            return Err("Baby name cannot be empty".to_string());
+            return Err(TrackerError::EmptyName);
         }
         Ok(Dejection {
             id: 0,
             baby_name: baby_name.trim().to_string(),
             dejection_type,
+            rash,
             notes: notes.filter(|n| !n.trim().is_empty()),
             timestamp,
         })
@@ -146,9 +502,9 @@ impl Dejection {
 
 // --- Weight ---
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Weight {
-    pub id: u32,
+    pub id: u64,
     pub baby_name: String,
     pub weight_kg: f64,
     pub notes: Option<String>,
@@ -161,12 +517,12 @@ impl Weight {
         weight_kg: f64,
         notes: Option<String>,
         timestamp: NaiveDateTime,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, TrackerError> {
         if baby_name.trim().is_empty() {
-            return Err("Baby name cannot be empty".to_string());
+            return Err(TrackerError::EmptyName);
         }
         if weight_kg <= 0.0 {
-            return Err("Weight must be positive".to_string());
+            return Err(TrackerError::NonPositiveWeight);
         }
         Ok(Weight {
             id: 0,
@@ -178,36 +534,98 @@ impl Weight {
     }
 }
 
+// --- BabyMeta ---
+
+/// Per-baby profile data. Stored by name, independently of any events.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BabyMeta {
+    /// Display colour for the UI, e.g. `#ff8800`.
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub birthdate: Option<NaiveDate>,
+}
+
 // --- Unified timeline entry for day view ---
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct TimelineEntry {
-    pub id: u32,
+    pub id: u64,
     pub kind: &'static str,
     pub baby_name: String,
     pub subtype: String,
     pub amount_ml: Option<f64>,
+    pub leftover_ml: Option<f64>,
     pub duration_minutes: Option<u32>,
     pub weight_kg: Option<f64>,
+    pub rash: Option<bool>,
     pub notes: Option<String>,
+    pub source: Option<String>,
+    pub fussiness: Option<u8>,
+    #[serde(serialize_with = "serialize_full_timestamp")]
     pub timestamp: NaiveDateTime,
+    /// Running ml total for the day up to and including this feeding. Only set
+    /// by `Store::timeline_for_day_cumulative`, and omitted from JSON otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cumulative_ml: Option<f64>,
+    /// Baby's age in days at this entry. Set by `Store::timeline_for_day` when
+    /// a birthdate is known, and omitted from JSON otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_days: Option<u32>,
+    /// Custom display label for a dejection (see `Store::set_dejection_labels`).
+    /// Set by `Store::timeline_for_day`, and omitted from JSON otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Always `YYYY-MM-DDTHH:MM:SS`, so two entries in the same minute stay distinct
+/// and sub-second input never leaks a fractional part into the output.
+fn serialize_full_timestamp<S: Serializer>(ts: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&ts.format("%Y-%m-%dT%H:%M:%S"))
+}
+
+/// `amount_ml` less `leftover_ml`, floored at zero. Shared by `Feeding` and
+/// `TimelineEntry` so intake totals and running totals always agree.
+fn consumed_ml(amount_ml: Option<f64>, leftover_ml: Option<f64>) -> Option<f64> {
+    amount_ml.map(|ml| (ml - leftover_ml.unwrap_or(0.0)).max(0.0))
+}
+
+/// Sorts entries by `TimelineEntry::sort_key`.
+pub fn sort_timeline(entries: &mut [TimelineEntry]) {
+    entries.sort_by_key(TimelineEntry::sort_key);
 }
 
 impl TimelineEntry {
+    /// Timestamp, then `kind`, then `id`, so events logged in the same minute
+    /// always sort the same way. Deliberately not an `Ord` impl: equality stays
+    /// field-wise.
+    pub fn sort_key(&self) -> (NaiveDateTime, &'static str, u64) {
+        (self.timestamp, self.kind, self.id)
+    }
+
+    /// Same as `Feeding::consumed_ml`; `None` for non-feeding entries.
+    pub fn consumed_ml(&self) -> Option<f64> {
+        consumed_ml(self.amount_ml, self.leftover_ml)
+    }
+
     pub fn from_feeding(f: &Feeding) -> Self {
         TimelineEntry {
             id: f.id,
             kind: "feeding",
             baby_name: f.baby_name.clone(),
-            subtype: serde_json::to_string(&f.feeding_type)
-                .unwrap_or_default()
-                .trim_matches('"')
-                .to_string(),
+            subtype: f.feeding_type.wire_name().to_string(),
             amount_ml: f.amount_ml,
+            leftover_ml: f.leftover_ml,
             duration_minutes: f.duration_minutes,
             weight_kg: None,
+            rash: None,
             notes: f.notes.clone(),
+            source: f.source.clone(),
+            fussiness: f.fussiness,
             timestamp: f.timestamp,
+            cumulative_ml: None,
+            age_days: None,
+            label: None,
         }
     }
 
@@ -221,10 +639,17 @@ impl TimelineEntry {
                 .trim_matches('"')
                 .to_string(),
             amount_ml: None,
+            leftover_ml: None,
             duration_minutes: None,
             weight_kg: None,
+            rash: Some(d.rash),
             notes: d.notes.clone(),
+            source: None,
+            fussiness: None,
             timestamp: d.timestamp,
+            cumulative_ml: None,
+            age_days: None,
+            label: None,
         }
     }
 
@@ -235,10 +660,17 @@ impl TimelineEntry {
             baby_name: w.baby_name.clone(),
             subtype: "weight".to_string(),
             amount_ml: None,
+            leftover_ml: None,
             duration_minutes: None,
             weight_kg: Some(w.weight_kg),
+            rash: None,
             notes: w.notes.clone(),
+            source: None,
+            fussiness: None,
             timestamp: w.timestamp,
+            cumulative_ml: None,
+            age_days: None,
+            label: None,
         }
     }
 }
@@ -246,7 +678,7 @@ impl TimelineEntry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDate;
+    use chrono::{NaiveDate, Timelike};
 
     fn ts(h: u32, m: u32) -> NaiveDateTime {
         NaiveDate::from_ymd_opt(2026, 2, 15)
@@ -255,8 +687,105 @@ mod tests {
             .unwrap()
     }
 
+    // --- Consistency warnings ---
+
+    fn feeding(ft: FeedingType, amount_ml: Option<f64>, duration_minutes: Option<u32>) -> Feeding {
+        Feeding::new("Emma".to_string(), ft, amount_ml, None, duration_minutes, None, None, None, ts(8, 0)).unwrap()
+    }
+
+    #[test]
+    fn consistency_bottle_without_amount() {
+        assert_eq!(feeding(FeedingType::Bottle, None, Some(10)).consistency_warnings(), vec!["a bottle with no amount"]);
+        assert!(feeding(FeedingType::Bottle, Some(90.0), None).consistency_warnings().is_empty());
+    }
+
+    #[test]
+    fn consistency_breast_with_only_amount() {
+        assert_eq!(
+            feeding(FeedingType::BreastRight, Some(40.0), None).consistency_warnings(),
+            vec!["a breast feeding with an amount but no duration"]
+        );
+        assert!(feeding(FeedingType::BreastLeft, Some(40.0), Some(10)).consistency_warnings().is_empty());
+        assert!(feeding(FeedingType::BreastLeft, None, Some(10)).consistency_warnings().is_empty());
+    }
+
+    #[test]
+    fn consistency_solid_with_volume() {
+        assert_eq!(feeding(FeedingType::Solid, Some(30.0), None).consistency_warnings(), vec!["a solid with a volume in ml"]);
+        assert!(feeding(FeedingType::Solid, None, None).consistency_warnings().is_empty());
+    }
+
+    // --- Locale ---
+
+    #[test]
+    fn locale_parse_falls_back_to_english() {
+        assert_eq!(Locale::parse("es"), Locale::Es);
+        assert_eq!(Locale::parse("es-MX"), Locale::Es);
+        assert_eq!(Locale::parse("ES_es"), Locale::Es);
+        assert_eq!(Locale::parse("en-GB"), Locale::En);
+        assert_eq!(Locale::parse("fr"), Locale::En);
+        assert_eq!(Locale::parse(""), Locale::En);
+    }
+
+    #[test]
+    fn display_localized_spanish() {
+        assert_eq!(FeedingType::Bottle.display_localized(Locale::Es), "Biberón");
+        assert_eq!(FeedingType::BreastLeft.display_localized(Locale::Es), "Pecho (izquierdo)");
+        assert_eq!(DejectionType::Poop.display_localized(Locale::Es), "Caca");
+        assert_eq!(FeedingType::Bottle.to_string(), "Bottle");
+        assert_eq!(DejectionType::Urine.to_string(), "Urine");
+    }
+
+    // --- UnitSystem ---
+
+    #[test]
+    fn unit_system_formats() {
+        assert_eq!(UnitSystem::Metric.format_volume(473.0), "473 ml");
+        assert_eq!(UnitSystem::Imperial.format_volume(473.0), "16 oz");
+        assert_eq!(UnitSystem::Metric.format_weight(3.49), "3.49 kg");
+        assert_eq!(UnitSystem::Imperial.format_weight(3.49), "7 lb 11 oz");
+        assert_eq!(UnitSystem::Imperial.format_weight(3.6), "7 lb 15 oz");
+    }
+
+    #[test]
+    fn unit_system_parse() {
+        assert_eq!(UnitSystem::parse("Imperial").unwrap(), UnitSystem::Imperial);
+        assert_eq!(UnitSystem::parse("metric").unwrap(), UnitSystem::Metric);
+        assert!(UnitSystem::parse("furlongs").is_err());
+    }
+
+    // --- NameMatching ---
+
+    #[test]
+    fn name_matching_modes() {
+        assert!(NameMatching::Exact.matches(None, "Emma"));
+        assert!(NameMatching::Exact.matches(Some("Emma"), "Emma"));
+        assert!(!NameMatching::Exact.matches(Some("emma"), "Emma"));
+        assert!(NameMatching::CaseInsensitive.matches(Some("emma"), "Emma"));
+        assert!(NameMatching::CaseInsensitive.matches(Some("ZOÉ"), "Zoé"));
+        assert!(!NameMatching::CaseInsensitive.matches(Some("Noah"), "Emma"));
+    }
+
+    #[test]
+    fn name_matching_parse() {
+        assert_eq!(NameMatching::parse("exact").unwrap(), NameMatching::Exact);
+        assert_eq!(NameMatching::parse("Case-Insensitive").unwrap(), NameMatching::CaseInsensitive);
+        assert!(NameMatching::parse("fuzzy").is_err());
+    }
+
     // --- FeedingType parsing ---
 
+    #[test]
+    fn feeding_type_all_lists_builtins_in_order() {
+        assert_eq!(
+            *FeedingType::all(),
+            [FeedingType::BreastLeft, FeedingType::BreastRight, FeedingType::Bottle, FeedingType::Solid]
+        );
+        for ft in FeedingType::all() {
+            assert_eq!(&FeedingType::parse(ft.wire_name()).unwrap(), ft);
+        }
+    }
+
     #[test]
     fn parse_feeding_type_full_names() {
         assert_eq!(FeedingType::parse("breast-left").unwrap(), FeedingType::BreastLeft);
@@ -281,10 +810,31 @@ mod tests {
 
     #[test]
     fn parse_feeding_type_invalid() {
-        assert!(FeedingType::parse("juice").is_err());
+        assert_eq!(FeedingType::parse("juice"), Err(TrackerError::UnknownFeedingType("juice".to_string(), None)));
         assert!(FeedingType::parse("").is_err());
     }
 
+    #[test]
+    fn parse_feeding_type_suggests_closest_name() {
+        let err = FeedingType::parse("breastleft").unwrap_err();
+        assert_eq!(err, TrackerError::UnknownFeedingType("breastleft".to_string(), Some("breast-left")));
+        assert!(err.to_string().contains("Did you mean 'breast-left'?"));
+
+        let err = FeedingType::parse("botle").unwrap_err();
+        assert_eq!(err, TrackerError::UnknownFeedingType("botle".to_string(), Some("bottle")));
+
+        let err = FeedingType::parse("Breast-Rigth").unwrap_err();
+        assert_eq!(err, TrackerError::UnknownFeedingType("Breast-Rigth".to_string(), Some("breast-right")));
+    }
+
+    #[test]
+    fn levenshtein_distances() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("bottle", "bottle"), 0);
+        assert_eq!(levenshtein("botle", "bottle"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
     #[test]
     fn feeding_type_display() {
         assert_eq!(FeedingType::BreastLeft.to_string(), "Breast (Left)");
@@ -300,6 +850,25 @@ mod tests {
         assert_eq!(parsed, ft);
     }
 
+    #[test]
+    fn custom_feeding_type_serde_roundtrip() {
+        let ft = FeedingType::Other("water".to_string());
+        let json = serde_json::to_string(&ft).unwrap();
+        assert_eq!(json, r#"{"other":"water"}"#);
+        assert_eq!(serde_json::from_str::<FeedingType>(&json).unwrap(), ft);
+        assert_eq!(ft.to_string(), "water");
+        assert_eq!(ft.display_localized(Locale::Es), "water");
+        assert_eq!(ft.wire_name(), "water");
+    }
+
+    #[test]
+    fn parse_lenient_falls_back_to_other() {
+        assert_eq!(FeedingType::parse_lenient(" Juice ").unwrap(), FeedingType::Other("Juice".to_string()));
+        assert_eq!(FeedingType::parse_lenient("b").unwrap(), FeedingType::Bottle);
+        assert!(FeedingType::parse_lenient("  ").is_err());
+        assert!(FeedingType::parse("juice").is_err());
+    }
+
     // --- Feeding construction & validation ---
 
     #[test]
@@ -309,7 +878,10 @@ mod tests {
             FeedingType::Bottle,
             Some(120.0),
             None,
+            None,
             Some("Morning".to_string()),
+            None,
+            None,
             ts(8, 0),
         )
         .unwrap();
@@ -320,30 +892,50 @@ mod tests {
 
     #[test]
     fn feeding_new_trims_name() {
-        let f = Feeding::new("  Emma  ".to_string(), FeedingType::Bottle, None, None, None, ts(8, 0)).unwrap();
+        let f = Feeding::new("  Emma  ".to_string(), FeedingType::Bottle, None, None, None, None, None, None, ts(8, 0)).unwrap();
         assert_eq!(f.baby_name, "Emma");
     }
 
     #[test]
     fn feeding_new_empty_name_rejected() {
-        assert!(Feeding::new("".to_string(), FeedingType::Bottle, None, None, None, ts(8, 0)).is_err());
-        assert!(Feeding::new("   ".to_string(), FeedingType::Bottle, None, None, None, ts(8, 0)).is_err());
+        assert!(Feeding::new("".to_string(), FeedingType::Bottle, None, None, None, None, None, None, ts(8, 0)).is_err());
+        assert!(Feeding::new("   ".to_string(), FeedingType::Bottle, None, None, None, None, None, None, ts(8, 0)).is_err());
     }
 
     #[test]
     fn feeding_new_negative_amount_rejected() {
-        assert!(Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(-10.0), None, None, ts(8, 0)).is_err());
+        let err = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(-10.0), None, None, None, None, None, ts(8, 0)).unwrap_err();
+        assert_eq!(err, TrackerError::NegativeAmount);
+    }
+
+    #[test]
+    fn feeding_new_fussiness_range() {
+        let new = |level| Feeding::new("Emma".to_string(), FeedingType::BreastLeft, None, None, Some(10), None, None, level, ts(8, 0));
+        assert_eq!(new(Some(0)).unwrap().fussiness, Some(0));
+        assert_eq!(new(Some(MAX_FUSSINESS)).unwrap().fussiness, Some(5));
+        assert_eq!(new(None).unwrap().fussiness, None);
+        assert_eq!(new(Some(6)).unwrap_err(), TrackerError::FussinessOutOfRange(6));
+    }
+
+    #[test]
+    fn feeding_new_leftover_must_fit_in_amount() {
+        let new = |amount, leftover| Feeding::new("Emma".to_string(), FeedingType::Bottle, amount, leftover, None, None, None, None, ts(8, 0));
+        assert_eq!(new(Some(120.0), Some(20.0)).unwrap().leftover_ml, Some(20.0));
+        assert!(new(Some(120.0), Some(120.0)).is_ok());
+        assert_eq!(new(Some(120.0), Some(130.0)).unwrap_err(), TrackerError::LeftoverExceedsAmount);
+        assert_eq!(new(None, Some(10.0)).unwrap_err(), TrackerError::LeftoverExceedsAmount);
+        assert_eq!(new(Some(120.0), Some(-5.0)).unwrap_err(), TrackerError::NegativeAmount);
     }
 
     #[test]
     fn feeding_new_blank_notes_become_none() {
-        let f = Feeding::new("Emma".to_string(), FeedingType::Solid, None, None, Some("  ".to_string()), ts(8, 0)).unwrap();
+        let f = Feeding::new("Emma".to_string(), FeedingType::Solid, None, None, None, Some("  ".to_string()), None, None, ts(8, 0)).unwrap();
         assert_eq!(f.notes, None);
     }
 
     #[test]
     fn feeding_serde_roundtrip() {
-        let f = Feeding::new("Emma".to_string(), FeedingType::BreastRight, None, Some(15), Some("Good latch".to_string()), ts(14, 30)).unwrap();
+        let f = Feeding::new("Emma".to_string(), FeedingType::BreastRight, None, None, Some(15), Some("Good latch".to_string()), None, None, ts(14, 30)).unwrap();
         let json = serde_json::to_string(&f).unwrap();
         let parsed: Feeding = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.baby_name, f.baby_name);
@@ -352,8 +944,93 @@ mod tests {
         assert_eq!(parsed.timestamp, f.timestamp);
     }
 
+    #[test]
+    fn feeding_source_serde_roundtrip() {
+        let f = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(90.0), None, None, None, Some("donor".to_string()), None, ts(8, 0)).unwrap();
+        let json = serde_json::to_string(&f).unwrap();
+        let parsed: Feeding = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.source.as_deref(), Some("donor"));
+
+        let legacy = r#"{"id":1,"baby_name":"Emma","feeding_type":"bottle","amount_ml":90.0,"duration_minutes":null,"notes":null,"timestamp":"2026-02-15T08:00:00"}"#;
+        let parsed: Feeding = serde_json::from_str(legacy).unwrap();
+        assert_eq!(parsed.source, None);
+    }
+
+    #[test]
+    fn feeding_new_blank_source_becomes_none() {
+        let f = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(90.0), None, None, None, Some(" ".to_string()), None, ts(8, 0)).unwrap();
+        assert_eq!(f.source, None);
+    }
+
+    // --- FeedingBuilder ---
+
+    #[test]
+    fn builder_matches_new() {
+        let built = FeedingBuilder::new()
+            .baby_name("Emma")
+            .feeding_type(FeedingType::Bottle)
+            .amount_ml(120.0)
+            .duration_minutes(10)
+            .notes("Morning")
+            .timestamp(ts(8, 0))
+            .build()
+            .unwrap();
+        let direct = Feeding::new(
+            "Emma".to_string(),
+            FeedingType::Bottle,
+            Some(120.0),
+            None,
+            Some(10),
+            Some("Morning".to_string()),
+            None,
+            None,
+            ts(8, 0),
+        )
+        .unwrap();
+        assert_eq!(built, direct);
+    }
+
+    #[test]
+    fn builder_leaves_optionals_unset() {
+        let f = FeedingBuilder::new()
+            .baby_name("Emma")
+            .feeding_type(FeedingType::BreastLeft)
+            .timestamp(ts(8, 0))
+            .build()
+            .unwrap();
+        assert_eq!(f.amount_ml, None);
+        assert_eq!(f.duration_minutes, None);
+        assert_eq!(f.notes, None);
+    }
+
+    #[test]
+    fn builder_runs_validation() {
+        let empty_name = FeedingBuilder::new().feeding_type(FeedingType::Bottle).timestamp(ts(8, 0)).build();
+        assert_eq!(empty_name.unwrap_err(), TrackerError::EmptyName);
+        let negative = FeedingBuilder::new()
+            .baby_name("Emma")
+            .feeding_type(FeedingType::Bottle)
+            .amount_ml(-5.0)
+            .timestamp(ts(8, 0))
+            .build();
+        assert_eq!(negative.unwrap_err(), TrackerError::NegativeAmount);
+    }
+
+    #[test]
+    fn builder_requires_type_and_timestamp() {
+        let no_type = FeedingBuilder::new().baby_name("Emma").timestamp(ts(8, 0)).build();
+        assert_eq!(no_type.unwrap_err(), TrackerError::MissingField("feeding_type"));
+        let no_time = FeedingBuilder::new().baby_name("Emma").feeding_type(FeedingType::Bottle).build();
+        assert_eq!(no_time.unwrap_err(), TrackerError::MissingField("timestamp"));
+    }
+
     // --- DejectionType parsing ---
 
+    #[test]
+    fn dejection_type_all_lists_every_variant() {
+        assert_eq!(*DejectionType::all(), [DejectionType::Urine, DejectionType::Poop]);
+    }
+
     #[test]
     fn parse_dejection_type_full_names() {
         assert_eq!(DejectionType::parse("urine").unwrap(), DejectionType::Urine);
@@ -392,7 +1069,7 @@ mod tests {
 
     #[test]
     fn dejection_new_valid() {
-        let d = Dejection::new("Emma".to_string(), DejectionType::Poop, Some("Soft".to_string()), ts(10, 0)).unwrap();
+        let d = Dejection::new("Emma".to_string(), DejectionType::Poop, false, Some("Soft".to_string()), ts(10, 0)).unwrap();
         assert_eq!(d.baby_name, "Emma");
         assert_eq!(d.dejection_type, DejectionType::Poop);
         assert_eq!(d.notes, Some("Soft".to_string()));
@@ -400,15 +1077,22 @@ mod tests {
 
     #[test]
     fn dejection_new_empty_name_rejected() {
-        assert!(Dejection::new("".to_string(), DejectionType::Urine, None, ts(10, 0)).is_err());
+        assert!(Dejection::new("".to_string(), DejectionType::Urine, false, None, ts(10, 0)).is_err());
     }
 
     #[test]
     fn dejection_new_blank_notes_become_none() {
-        let d = Dejection::new("Emma".to_string(), DejectionType::Urine, Some("  ".to_string()), ts(10, 0)).unwrap();
+        let d = Dejection::new("Emma".to_string(), DejectionType::Urine, false, Some("  ".to_string()), ts(10, 0)).unwrap();
         assert_eq!(d.notes, None);
     }
 
+    #[test]
+    fn dejection_rash_defaults_to_false_when_missing() {
+        let json = r#"{"id":1,"baby_name":"Emma","dejection_type":"poop","notes":null,"timestamp":"2026-02-15T10:00:00"}"#;
+        let d: Dejection = serde_json::from_str(json).unwrap();
+        assert!(!d.rash);
+    }
+
     // --- Weight ---
 
     #[test]
@@ -426,7 +1110,8 @@ mod tests {
 
     #[test]
     fn weight_new_zero_rejected() {
-        assert!(Weight::new("Emma".to_string(), 0.0, None, ts(8, 0)).is_err());
+        let err = Weight::new("Emma".to_string(), 0.0, None, ts(8, 0)).unwrap_err();
+        assert_eq!(err, TrackerError::NonPositiveWeight);
     }
 
     #[test]
@@ -455,7 +1140,7 @@ mod tests {
 
     #[test]
     fn timeline_entry_from_feeding() {
-        let mut f = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(120.0), None, None, ts(8, 0)).unwrap();
+        let mut f = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(120.0), None, None, None, None, None, ts(8, 0)).unwrap();
         f.id = 1;
         let e = TimelineEntry::from_feeding(&f);
         assert_eq!(e.kind, "feeding");
@@ -465,11 +1150,56 @@ mod tests {
 
     #[test]
     fn timeline_entry_from_dejection() {
-        let mut d = Dejection::new("Emma".to_string(), DejectionType::Poop, None, ts(9, 0)).unwrap();
+        let mut d = Dejection::new("Emma".to_string(), DejectionType::Poop, true, None, ts(9, 0)).unwrap();
         d.id = 2;
         let e = TimelineEntry::from_dejection(&d);
         assert_eq!(e.kind, "dejection");
         assert_eq!(e.subtype, "poop");
         assert_eq!(e.amount_ml, None);
+        assert_eq!(e.rash, Some(true));
+    }
+
+    #[test]
+    fn timeline_entry_serializes_full_seconds() {
+        let at = ts(9, 0).with_second(30).unwrap().with_nanosecond(250_000_000).unwrap();
+        let f = Feeding::new("Emma".to_string(), FeedingType::Bottle, None, None, None, None, None, None, at).unwrap();
+        let json = serde_json::to_value(TimelineEntry::from_feeding(&f)).unwrap();
+        assert_eq!(json["timestamp"], "2026-02-15T09:00:30");
+    }
+
+    #[test]
+    fn timeline_entry_order_ties_on_kind_then_id() {
+        let mut f = Feeding::new("Emma".to_string(), FeedingType::Bottle, None, None, None, None, None, None, ts(9, 0)).unwrap();
+        f.id = 1;
+        let mut d = Dejection::new("Emma".to_string(), DejectionType::Urine, false, None, ts(9, 0)).unwrap();
+        d.id = 3;
+        let mut w = Weight::new("Emma".to_string(), 3.5, None, ts(9, 0)).unwrap();
+        w.id = 2;
+        let mut f2 = f.clone();
+        f2.id = 4;
+        let mut earlier = Weight::new("Emma".to_string(), 3.5, None, ts(8, 59)).unwrap();
+        earlier.id = 5;
+
+        let mut entries = [
+            TimelineEntry::from_weight(&w),
+            TimelineEntry::from_feeding(&f2),
+            TimelineEntry::from_dejection(&d),
+            TimelineEntry::from_feeding(&f),
+            TimelineEntry::from_weight(&earlier),
+        ];
+        sort_timeline(&mut entries);
+        let order: Vec<(&str, u64)> = entries.iter().map(|e| (e.kind, e.id)).collect();
+        assert_eq!(order, vec![("weight", 5), ("dejection", 3), ("feeding", 1), ("feeding", 4), ("weight", 2)]);
+    }
+
+    #[test]
+    fn timeline_entry_equality_compares_every_field() {
+        let mut f = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(90.0), None, None, None, None, None, ts(9, 0)).unwrap();
+        f.id = 1;
+        let a = TimelineEntry::from_feeding(&f);
+        f.amount_ml = Some(120.0);
+        let b = TimelineEntry::from_feeding(&f);
+        assert_eq!(a.sort_key(), b.sort_key());
+        assert_ne!(a, b);
     }
 }
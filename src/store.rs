@@ -1,16 +1,136 @@
-use chrono::NaiveDateTime;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
 use serde::{Deserialize, Serialize};
 
-use crate::models::{Dejection, DejectionType, Feeding, FeedingType, TimelineEntry, Weight};
+use crate::error::TrackerError;
+use crate::models::{
+    sort_timeline, BabyMeta, Dejection, DejectionType, Feeding, FeedingPatch, FeedingType, Locale, NameMatching, TimelineEntry,
+    UnitSystem, Weight,
+};
+
+/// Rough energy density of breast milk and standard formula.
+const DEFAULT_KCAL_PER_ML: f64 = 0.67;
+/// Rough energy of a small serving of baby food.
+const DEFAULT_KCAL_PER_SOLID: f64 = 50.0;
+
+/// strftime pattern for timestamps in human-readable text until one is set.
+const DEFAULT_DISPLAY_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// First hour of each daypart in `report_by_daypart`. Night runs from
+/// `NIGHT_START_HOUR` to midnight and from midnight to `MORNING_START_HOUR`.
+const MORNING_START_HOUR: u32 = 6;
+const AFTERNOON_START_HOUR: u32 = 12;
+const EVENING_START_HOUR: u32 = 18;
+const NIGHT_START_HOUR: u32 = 22;
+
+/// Header of `to_bincode` output: magic bytes, then a format version. bincode
+/// ignores `#[serde(default)]`, so any field change to the stored types must
+/// bump the version; older blobs are then rejected rather than misread.
+const BINCODE_MAGIC: &[u8; 3] = b"BTK";
+const BINCODE_VERSION: u8 = 1;
+
+/// A weigh-in more than this fraction below the previous one is flagged as suspicious.
+const MAX_PLAUSIBLE_WEIGHT_DROP: f64 = 0.10;
+
+/// User preferences persisted alongside the data. Missing fields fall back to
+/// their defaults so older exports keep loading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct Settings {
+    kcal_per_ml: f64,
+    kcal_per_solid: f64,
+    locale: Locale,
+    name_matching: NameMatching,
+    /// Step that feeding amounts are rounded to on add/update; 0 disables.
+    volume_rounding: f64,
+    daily_goal_ml: Option<f64>,
+    unit_system: UnitSystem,
+    /// Accept unknown feeding type names as `FeedingType::Other`.
+    lenient_feeding_types: bool,
+    /// strftime pattern for timestamps in messages, checked on set.
+    display_format: String,
+    /// Display labels replacing the built-in urine/poop wording; `None` keeps it.
+    urine_label: Option<String>,
+    poop_label: Option<String>,
+    /// Accept only full `YYYY-MM-DDTHH:MM:SS` timestamps in the tracker.
+    strict_timestamps: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            kcal_per_ml: DEFAULT_KCAL_PER_ML,
+            kcal_per_solid: DEFAULT_KCAL_PER_SOLID,
+            locale: Locale::default(),
+            name_matching: NameMatching::default(),
+            volume_rounding: 0.0,
+            daily_goal_ml: None,
+            unit_system: UnitSystem::default(),
+            lenient_feeding_types: false,
+            display_format: DEFAULT_DISPLAY_FORMAT.to_string(),
+            urine_label: None,
+            poop_label: None,
+            strict_timestamps: false,
+        }
+    }
+}
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Store {
     feedings: Vec<Feeding>,
     #[serde(default)]
     dejections: Vec<Dejection>,
     #[serde(default)]
     weights: Vec<Weight>,
-    next_id: u32,
+    /// One counter for all three collections. Ids are `u64` end to end (models,
+    /// store and the wasm API, where they arrive as `BigInt`) so no layer ever
+    /// narrows them.
+    next_id: u64,
+    #[serde(default)]
+    settings: Settings,
+    /// Keyed by baby name; a `BTreeMap` so exports stay byte-stable.
+    #[serde(default)]
+    babies: BTreeMap<String, BabyMeta>,
+    /// Set by every change, cleared when the store is exported; never persisted.
+    /// Atomic because exporting only borrows the store, and a `Cell` would make
+    /// `Store` lose `Sync`. No other memory depends on it, so `Relaxed` suffices.
+    #[serde(skip)]
+    dirty: AtomicBool,
+    /// Set by `from_json` when the export's `meta` does not match its records.
+    #[serde(skip)]
+    load_warning: Option<String>,
+}
+
+/// Integrity data appended to `to_json` output. Recomputed on every export and
+/// only ever compared on load, never trusted over the records themselves.
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportMeta {
+    entry_count: usize,
+    content_hash: String,
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    #[serde(flatten)]
+    store: &'a Store,
+    meta: ExportMeta,
+}
+
+#[derive(Deserialize)]
+struct JsonImport {
+    #[serde(flatten)]
+    store: Store,
+    #[serde(default)]
+    meta: Option<ExportMeta>,
+}
+
+impl Default for Store {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Store {
@@ -20,78 +140,587 @@ impl Store {
             dejections: Vec::new(),
             weights: Vec::new(),
             next_id: 1,
+            settings: Settings::default(),
+            babies: BTreeMap::new(),
+            dirty: AtomicBool::new(false),
+            load_warning: None,
+        }
+    }
+
+    /// Lenient load: if ids repeat across records (hand-edited or naively merged
+    /// files), every record is reindexed; a `next_id` at or below the highest id
+    /// is bumped past it. Use `from_json_strict` to reject such files instead.
+    /// A store repaired this way starts out dirty so the fix gets saved.
+    /// A `meta` section that disagrees with the records is reported through
+    /// `load_warning` rather than rejected.
+    pub fn from_json(json: &str) -> Result<Self, TrackerError> {
+        let import: JsonImport = serde_json::from_str(json).map_err(|e| TrackerError::InvalidData(e.to_string()))?;
+        let mut store = import.store;
+        store.load_warning = import.meta.and_then(|meta| store.integrity_mismatch(&meta));
+        store.repair_ids();
+        Ok(store)
+    }
+
+    /// Reindexes repeated ids, or bumps a `next_id` at or below the highest id
+    /// past it, marking the store dirty either way.
+    fn repair_ids(&mut self) {
+        if self.first_duplicate_id().is_some() {
+            self.reindex();
+        } else if self.next_id <= self.max_id() {
+            self.next_id = self.max_id().saturating_add(1);
+            self.dirty.store(true, Ordering::Relaxed);
         }
     }
 
-    pub fn from_json(json: &str) -> Result<Self, String> {
-        serde_json::from_str(json).map_err(|e| format!("Invalid data: {}", e))
+    /// Like `from_json`, but repeated ids, a `next_id` that would reissue an
+    /// existing id, or a mismatched `meta` section are errors.
+    pub fn from_json_strict(json: &str) -> Result<Self, TrackerError> {
+        let import: JsonImport = serde_json::from_str(json).map_err(|e| TrackerError::InvalidData(e.to_string()))?;
+        let store = import.store;
+        if let Some(mismatch) = import.meta.and_then(|meta| store.integrity_mismatch(&meta)) {
+            return Err(TrackerError::InvalidData(mismatch));
+        }
+        if let Some(id) = store.first_duplicate_id() {
+            return Err(TrackerError::InvalidData(format!("id {} is used by more than one record", id)));
+        }
+        if store.next_id <= store.max_id() {
+            return Err(TrackerError::InvalidData(format!(
+                "next_id {} is not above the highest id {}",
+                store.next_id,
+                store.max_id()
+            )));
+        }
+        Ok(store)
     }
 
+    /// Byte-stable for the same data: every serialized type is a derived
+    /// struct with a fixed field order, and records keep their stored order.
+    /// Keep new persisted state out of `HashMap`s so this stays true.
+    /// Ends with a `meta` section (entry count and content hash) that
+    /// `from_json` checks. Also clears `is_dirty`.
     pub fn to_json(&self) -> String {
-        serde_json::to_string(self).expect("Store serialization should never fail")
+        self.dirty.store(false, Ordering::Relaxed);
+        let meta = ExportMeta {
+            entry_count: self.entry_count(),
+            content_hash: self.content_hash(),
+        };
+        serde_json::to_string(&JsonExport { store: self, meta }).expect("Store serialization should never fail")
+    }
+
+    // --- Integrity ---
+
+    /// Records across all three collections.
+    pub fn entry_count(&self) -> usize {
+        self.feedings.len() + self.dejections.len() + self.weights.len()
+    }
+
+    /// Why the last `from_json` distrusted its input's `meta` section, if it did.
+    pub fn load_warning(&self) -> Option<&str> {
+        self.load_warning.as_deref()
+    }
+
+    /// 64-bit FNV-1a of the serialized store (without `meta`), as hex. Not
+    /// cryptographic; it catches truncation and casual edits, nothing more.
+    fn content_hash(&self) -> String {
+        let body = serde_json::to_string(self).expect("Store serialization should never fail");
+        let hash = body
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3));
+        format!("{:016x}", hash)
+    }
+
+    fn integrity_mismatch(&self, meta: &ExportMeta) -> Option<String> {
+        if meta.entry_count != self.entry_count() {
+            return Some(format!(
+                "export lists {} entries but {} were loaded; it may be truncated",
+                meta.entry_count,
+                self.entry_count()
+            ));
+        }
+        if meta.content_hash != self.content_hash() {
+            return Some("export content does not match its hash; it may have been edited or corrupted".to_string());
+        }
+        None
+    }
+
+    /// `to_json` of a copy holding only `name`'s records and profile, for
+    /// sharing one child with a caregiver. Settings are kept; `next_id` is
+    /// recomputed from the surviving ids.
+    pub fn export_baby_json(&self, name: &str) -> String {
+        let wanted = Some(name);
+        let mut subset = Store {
+            feedings: self.feedings.iter().filter(|f| self.name_matches(wanted, &f.baby_name)).cloned().collect(),
+            dejections: self.dejections.iter().filter(|d| self.name_matches(wanted, &d.baby_name)).cloned().collect(),
+            weights: self.weights.iter().filter(|w| self.name_matches(wanted, &w.baby_name)).cloned().collect(),
+            next_id: 1,
+            settings: self.settings.clone(),
+            babies: self
+                .babies
+                .iter()
+                .filter(|(baby, _)| self.name_matches(wanted, baby))
+                .map(|(baby, meta)| (baby.clone(), meta.clone()))
+                .collect(),
+            dirty: AtomicBool::new(false),
+            load_warning: None,
+        };
+        subset.next_id = subset.max_id().saturating_add(1);
+        subset.to_json()
+    }
+
+    /// Hand-maintained JSON Schema (draft-07) for the `to_json` format. Keep it in
+    /// step with the serde attributes on `Store`, `Settings` and the models.
+    pub fn json_schema() -> String {
+        let timestamp = serde_json::json!({
+            "type": "string",
+            "pattern": "^\\d{4}-\\d{2}-\\d{2}T\\d{2}:\\d{2}:\\d{2}(\\.\\d+)?$"
+        });
+        let nullable_string = serde_json::json!({ "type": ["string", "null"] });
+        let id = serde_json::json!({ "type": "integer", "minimum": 0 });
+
+        serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Baby tracker store",
+            "type": "object",
+            "required": ["feedings", "next_id"],
+            "properties": {
+                "feedings": { "type": "array", "items": { "$ref": "#/definitions/feeding" } },
+                "dejections": { "type": "array", "items": { "$ref": "#/definitions/dejection" } },
+                "weights": { "type": "array", "items": { "$ref": "#/definitions/weight" } },
+                "next_id": id,
+                "settings": { "$ref": "#/definitions/settings" },
+                "babies": { "type": "object", "additionalProperties": { "$ref": "#/definitions/baby_meta" } },
+                "meta": {
+                    "type": "object",
+                    "properties": {
+                        "entry_count": { "type": "integer", "minimum": 0 },
+                        "content_hash": { "type": "string", "pattern": "^[0-9a-f]{16}$" }
+                    }
+                }
+            },
+            "definitions": {
+                "feeding": {
+                    "type": "object",
+                    "required": ["id", "baby_name", "feeding_type", "timestamp"],
+                    "properties": {
+                        "id": id,
+                        "baby_name": { "type": "string", "minLength": 1 },
+                        "feeding_type": {
+                            "oneOf": [
+                                { "enum": ["breast-left", "breast-right", "bottle", "solid"] },
+                                {
+                                    "type": "object",
+                                    "required": ["other"],
+                                    "properties": { "other": { "type": "string", "minLength": 1 } },
+                                    "additionalProperties": false
+                                }
+                            ]
+                        },
+                        "amount_ml": { "type": ["number", "null"], "minimum": 0 },
+                        "leftover_ml": { "type": ["number", "null"], "minimum": 0 },
+                        "duration_minutes": { "type": ["integer", "null"], "minimum": 0 },
+                        "notes": nullable_string,
+                        "source": nullable_string,
+                        "fussiness": { "type": ["integer", "null"], "minimum": 0, "maximum": 5 },
+                        "timestamp": timestamp
+                    }
+                },
+                "dejection": {
+                    "type": "object",
+                    "required": ["id", "baby_name", "dejection_type", "timestamp"],
+                    "properties": {
+                        "id": id,
+                        "baby_name": { "type": "string", "minLength": 1 },
+                        "dejection_type": { "enum": ["urine", "poop"] },
+                        "rash": { "type": "boolean" },
+                        "notes": nullable_string,
+                        "timestamp": timestamp
+                    }
+                },
+                "weight": {
+                    "type": "object",
+                    "required": ["id", "baby_name", "weight_kg", "timestamp"],
+                    "properties": {
+                        "id": id,
+                        "baby_name": { "type": "string", "minLength": 1 },
+                        "weight_kg": { "type": "number", "exclusiveMinimum": 0 },
+                        "notes": nullable_string,
+                        "timestamp": timestamp
+                    }
+                },
+                "settings": {
+                    "type": "object",
+                    "properties": {
+                        "kcal_per_ml": { "type": "number" },
+                        "kcal_per_solid": { "type": "number" },
+                        "locale": { "enum": ["en", "es"] },
+                        "name_matching": { "enum": ["exact", "case-insensitive"] },
+                        "volume_rounding": { "type": "number", "minimum": 0 },
+                        "daily_goal_ml": { "type": ["number", "null"], "exclusiveMinimum": 0 },
+                        "unit_system": { "enum": ["metric", "imperial"] },
+                        "lenient_feeding_types": { "type": "boolean" },
+                        "display_format": { "type": "string", "minLength": 1 },
+                        "urine_label": nullable_string,
+                        "poop_label": nullable_string,
+                        "strict_timestamps": { "type": "boolean" }
+                    }
+                },
+                "baby_meta": {
+                    "type": "object",
+                    "properties": {
+                        "color": nullable_string,
+                        "birthdate": { "type": ["string", "null"], "pattern": "^\\d{4}-\\d{2}-\\d{2}$" }
+                    }
+                }
+            }
+        })
+        .to_string()
+    }
+
+    /// Removes every feeding, dejection and weight in `[start, end)` for the baby
+    /// (or all babies), returning how many records were removed.
+    pub fn delete_range(&mut self, baby_name: Option<&str>, start: NaiveDateTime, end: NaiveDateTime) -> usize {
+        let matching = self.settings.name_matching;
+        let doomed = |n: &str, ts: NaiveDateTime| ts >= start && ts < end && matching.matches(baby_name, n);
+        let before = self.entry_count();
+        self.feedings.retain(|f| !doomed(&f.baby_name, f.timestamp));
+        self.dejections.retain(|d| !doomed(&d.baby_name, d.timestamp));
+        self.weights.retain(|w| !doomed(&w.baby_name, w.timestamp));
+        let removed = before - self.entry_count();
+        self.mark_dirty_if(removed > 0);
+        removed
+    }
+
+    /// Drops every record dated before `cutoff`, for all babies, returning how
+    /// many were removed. With `keep_weights`, weigh-ins are kept regardless of
+    /// age so growth history survives.
+    pub fn prune_older_than(&mut self, cutoff: NaiveDateTime, keep_weights: bool) -> usize {
+        let before = self.entry_count();
+        self.feedings.retain(|f| f.timestamp >= cutoff);
+        self.dejections.retain(|d| d.timestamp >= cutoff);
+        if !keep_weights {
+            self.weights.retain(|w| w.timestamp >= cutoff);
+        }
+        let removed = before - self.entry_count();
+        self.mark_dirty_if(removed > 0);
+        removed
+    }
+
+    /// Removes every record of `kind` (`"feeding"`, `"dejection"` or `"weight"`)
+    /// for the baby (or all babies), returning how many were removed. An unknown
+    /// kind removes nothing.
+    pub fn delete_by_kind(&mut self, kind: &str, baby_name: Option<&str>) -> usize {
+        let matching = self.settings.name_matching;
+        let doomed = |n: &str| matching.matches(baby_name, n);
+        let removed = match kind {
+            "feeding" => {
+                let before = self.feedings.len();
+                self.feedings.retain(|f| !doomed(&f.baby_name));
+                before - self.feedings.len()
+            }
+            "dejection" => {
+                let before = self.dejections.len();
+                self.dejections.retain(|d| !doomed(&d.baby_name));
+                before - self.dejections.len()
+            }
+            "weight" => {
+                let before = self.weights.len();
+                self.weights.retain(|w| !doomed(&w.baby_name));
+                before - self.weights.len()
+            }
+            _ => 0,
+        };
+        self.mark_dirty_if(removed > 0);
+        removed
+    }
+
+    /// Removes every record and restarts ids at 1. Settings and baby metadata
+    /// are kept.
+    pub fn clear(&mut self) {
+        let changed = self.entry_count() > 0 || self.next_id != 1;
+        self.feedings.clear();
+        self.dejections.clear();
+        self.weights.clear();
+        self.next_id = 1;
+        self.mark_dirty_if(changed);
+    }
+
+    // --- Change tracking ---
+
+    /// True when anything changed since the store was created, loaded or last
+    /// exported with `to_json`/`to_bincode`. Lets the UI skip redundant saves.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    fn mark_dirty_if(&self, changed: bool) {
+        if changed {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
+    // --- Compact binary (bincode) ---
+
+    /// Prefixed with `BINCODE_MAGIC` and `BINCODE_VERSION`. Also clears
+    /// `is_dirty`, like `to_json`.
+    pub fn to_bincode(&self) -> Vec<u8> {
+        self.dirty.store(false, Ordering::Relaxed);
+        let mut out = BINCODE_MAGIC.to_vec();
+        out.push(BINCODE_VERSION);
+        bincode::serialize_into(&mut out, self).expect("Store serialization should never fail");
+        out
+    }
+
+    /// Rejects data without the header or from another format version, then
+    /// repairs ids the same way `from_json` does.
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, TrackerError> {
+        let payload = match bytes.strip_prefix(BINCODE_MAGIC) {
+            Some([version, payload @ ..]) if *version == BINCODE_VERSION => payload,
+            Some([version, ..]) => {
+                return Err(TrackerError::InvalidData(format!(
+                    "unsupported binary export version {} (expected {})",
+                    version, BINCODE_VERSION
+                )))
+            }
+            _ => return Err(TrackerError::InvalidData("not a baby tracker binary export".to_string())),
+        };
+        let mut store: Store = bincode::deserialize(payload).map_err(|e| TrackerError::InvalidData(e.to_string()))?;
+        store.repair_ids();
+        Ok(store)
+    }
+
+    // --- Newline-delimited JSON (one record per line) ---
+
+    /// Emits a `meta` line carrying `next_id`, settings and baby metadata, then one line per
+    /// record tagged with its kind.
+    pub fn to_ndjson(&self) -> String {
+        let meta = NdjsonLine::Meta {
+            next_id: self.next_id,
+            settings: Cow::Borrowed(&self.settings),
+            babies: Cow::Borrowed(&self.babies),
+        };
+        let lines = std::iter::once(meta)
+            .chain(self.feedings.iter().map(|f| NdjsonLine::Feeding(Cow::Borrowed(f))))
+            .chain(self.dejections.iter().map(|d| NdjsonLine::Dejection(Cow::Borrowed(d))))
+            .chain(self.weights.iter().map(|w| NdjsonLine::Weight(Cow::Borrowed(w))));
+        let mut out = String::new();
+        for line in lines {
+            out.push_str(&serde_json::to_string(&line).expect("Record serialization should never fail"));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Blank lines are skipped. Without a `meta` line, `next_id` follows the highest id.
+    pub fn from_ndjson(s: &str) -> Result<Self, TrackerError> {
+        let mut store = Store::new();
+        let mut next_id = None;
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parsed: NdjsonLine = serde_json::from_str(line)
+                .map_err(|e| TrackerError::InvalidData(format!("line {}: {}", i + 1, e)))?;
+            match parsed {
+                NdjsonLine::Meta { next_id: n, settings, babies } => {
+                    next_id = Some(n);
+                    store.settings = settings.into_owned();
+                    store.babies = babies.into_owned();
+                }
+                NdjsonLine::Feeding(f) => store.feedings.push(f.into_owned()),
+                NdjsonLine::Dejection(d) => store.dejections.push(d.into_owned()),
+                NdjsonLine::Weight(w) => store.weights.push(w.into_owned()),
+            }
+        }
+        store.next_id = next_id.unwrap_or_else(|| store.max_id().saturating_add(1));
+        Ok(store)
+    }
+
+    // --- Id allocation ---
+
+    /// Highest id in use across all collections, or 0 when the store is empty.
+    pub fn max_id(&self) -> u64 {
+        self.feedings
+            .iter()
+            .map(|f| f.id)
+            .chain(self.dejections.iter().map(|d| d.id))
+            .chain(self.weights.iter().map(|w| w.id))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Smallest id carried by more than one record, across all collections.
+    fn first_duplicate_id(&self) -> Option<u64> {
+        let mut ids: Vec<u64> = self
+            .feedings
+            .iter()
+            .map(|f| f.id)
+            .chain(self.dejections.iter().map(|d| d.id))
+            .chain(self.weights.iter().map(|w| w.id))
+            .collect();
+        ids.sort();
+        ids.windows(2).find(|w| w[0] == w[1]).map(|w| w[0])
+    }
+
+    /// Reassigns ids 1..=n to every record, keeping their relative id order,
+    /// and resets `next_id` to follow them.
+    pub fn reindex(&mut self) {
+        let mut ids: Vec<&mut u64> = self
+            .feedings
+            .iter_mut()
+            .map(|f| &mut f.id)
+            .chain(self.dejections.iter_mut().map(|d| &mut d.id))
+            .chain(self.weights.iter_mut().map(|w| &mut w.id))
+            .collect();
+        ids.sort_by_key(|id| **id);
+        let mut next = 1;
+        for id in ids {
+            *id = next;
+            next += 1;
+        }
+        self.next_id = next;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Hands out the next id, or `IdsExhausted` once the counter cannot advance.
+    fn allocate_id(&mut self) -> Result<u64, TrackerError> {
+        let id = self.next_id;
+        self.next_id = id.checked_add(1).ok_or(TrackerError::IdsExhausted)?;
+        self.dirty.store(true, Ordering::Relaxed);
+        Ok(id)
+    }
+
+    /// `IdsExhausted` unless `n` more ids can be handed out, so batch inserts can
+    /// fail before storing anything.
+    pub fn ensure_ids_available(&self, n: usize) -> Result<(), TrackerError> {
+        let n = u64::try_from(n).map_err(|_| TrackerError::IdsExhausted)?;
+        self.next_id.checked_add(n).map(|_| ()).ok_or(TrackerError::IdsExhausted)
     }
 
     // --- Feeding CRUD ---
 
-    pub fn add_feeding(&mut self, mut feeding: Feeding) -> u32 {
-        feeding.id = self.next_id;
-        self.next_id += 1;
-        let id = feeding.id;
+    pub fn add_feeding(&mut self, mut feeding: Feeding) -> Result<u64, TrackerError> {
+        feeding.amount_ml = feeding.amount_ml.map(|ml| self.round_volume(ml));
+        feeding.leftover_ml = feeding.leftover_ml.map(|ml| self.round_volume(ml));
+        let id = self.allocate_id()?;
+        feeding.id = id;
         self.feedings.push(feeding);
-        id
+        Ok(id)
+    }
+
+    /// True when a feeding for the same baby with the same type and amount is
+    /// already stored within `window_seconds` of this one's timestamp.
+    pub fn is_duplicate_feeding(&self, feeding: &Feeding, window_seconds: i64) -> bool {
+        self.feedings.iter().any(|f| {
+            self.name_matches(Some(&feeding.baby_name), &f.baby_name)
+                && f.feeding_type == feeding.feeding_type
+                && f.amount_ml == feeding.amount_ml
+                && (f.timestamp - feeding.timestamp).num_seconds().abs() <= window_seconds
+        })
     }
 
-    pub fn delete_feeding(&mut self, id: u32) -> bool {
+    pub fn delete_feeding(&mut self, id: u64) -> bool {
         let before = self.feedings.len();
         self.feedings.retain(|f| f.id != id);
-        self.feedings.len() < before
+        let removed = self.feedings.len() < before;
+        self.mark_dirty_if(removed);
+        removed
     }
 
-    pub fn update_feeding(&mut self, id: u32, updated: Feeding) -> bool {
-        if let Some(f) = self.feedings.iter_mut().find(|f| f.id == id) {
-            f.feeding_type = updated.feeding_type;
-            f.amount_ml = updated.amount_ml;
-            f.duration_minutes = updated.duration_minutes;
-            f.notes = updated.notes;
-            f.timestamp = updated.timestamp;
-            true
-        } else {
-            false
+    pub fn update_feeding(&mut self, id: u64, updated: Feeding) -> bool {
+        self.update_feeding_reporting(id, updated).is_some()
+    }
+
+    /// Like `update_feeding`, but says whether anything changed: `None` when no
+    /// feeding has this id, `Some(false)` when the new values (after volume
+    /// rounding) equal the stored ones.
+    pub fn update_feeding_reporting(&mut self, id: u64, updated: Feeding) -> Option<bool> {
+        let amount_ml = updated.amount_ml.map(|ml| self.round_volume(ml));
+        let leftover_ml = updated.leftover_ml.map(|ml| self.round_volume(ml));
+        let f = self.feedings.iter_mut().find(|f| f.id == id)?;
+        let before = f.clone();
+        f.feeding_type = updated.feeding_type;
+        f.amount_ml = amount_ml;
+        f.leftover_ml = leftover_ml;
+        f.duration_minutes = updated.duration_minutes;
+        f.notes = updated.notes;
+        f.source = updated.source;
+        f.fussiness = updated.fussiness;
+        f.timestamp = updated.timestamp;
+        let changed = *f != before;
+        self.mark_dirty_if(changed);
+        Some(changed)
+    }
+
+    /// Applies only the fields set in `patch`; see `FeedingPatch`. The merged
+    /// feeding is validated like a new one, so e.g. lowering the amount below the
+    /// stored leftover is rejected and nothing changes. Ok(false) when no feeding
+    /// has this id.
+    pub fn patch_feeding(&mut self, id: u64, patch: FeedingPatch) -> Result<bool, TrackerError> {
+        let Some(index) = self.feedings.iter().position(|f| f.id == id) else {
+            return Ok(false);
+        };
+        let current = &self.feedings[index];
+        let mut merged = Feeding::new(
+            current.baby_name.clone(),
+            patch.feeding_type.unwrap_or_else(|| current.feeding_type.clone()),
+            patch.amount_ml.or(current.amount_ml),
+            patch.leftover_ml.or(current.leftover_ml),
+            patch.duration_minutes.or(current.duration_minutes),
+            patch.notes.or_else(|| current.notes.clone()),
+            patch.source.or_else(|| current.source.clone()),
+            patch.fussiness.or(current.fussiness),
+            patch.timestamp.unwrap_or(current.timestamp),
+        )?;
+        merged.id = id;
+        if let Some(ml) = patch.amount_ml {
+            merged.amount_ml = Some(self.round_volume(ml));
         }
+        if let Some(ml) = patch.leftover_ml {
+            merged.leftover_ml = Some(self.round_volume(ml));
+        }
+        let changed = self.feedings[index] != merged;
+        self.feedings[index] = merged;
+        self.mark_dirty_if(changed);
+        Ok(true)
     }
 
     pub fn list_feedings(&self, baby_name: Option<&str>, limit: usize) -> Vec<&Feeding> {
         let mut result: Vec<&Feeding> = self
             .feedings
             .iter()
-            .filter(|f| baby_name.map_or(true, |name| f.baby_name == name))
+            .filter(|f| self.name_matches(baby_name, &f.baby_name))
             .collect();
-        result.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        result.sort_by_key(|f| std::cmp::Reverse(f.timestamp));
         result.truncate(limit);
         result
     }
 
     // --- Dejection CRUD ---
 
-    pub fn add_dejection(&mut self, mut dejection: Dejection) -> u32 {
-        dejection.id = self.next_id;
-        self.next_id += 1;
-        let id = dejection.id;
+    pub fn add_dejection(&mut self, mut dejection: Dejection) -> Result<u64, TrackerError> {
+        let id = self.allocate_id()?;
+        dejection.id = id;
         self.dejections.push(dejection);
-        id
+        Ok(id)
     }
 
-    pub fn delete_dejection(&mut self, id: u32) -> bool {
+    pub fn delete_dejection(&mut self, id: u64) -> bool {
         let before = self.dejections.len();
         self.dejections.retain(|d| d.id != id);
-        self.dejections.len() < before
+        let removed = self.dejections.len() < before;
+        self.mark_dirty_if(removed);
+        removed
     }
 
-    pub fn update_dejection(&mut self, id: u32, updated: Dejection) -> bool {
+    pub fn update_dejection(&mut self, id: u64, updated: Dejection) -> bool {
         if let Some(d) = self.dejections.iter_mut().find(|d| d.id == id) {
+            let before = d.clone();
             d.dejection_type = updated.dejection_type;
+            d.rash = updated.rash;
             d.notes = updated.notes;
             d.timestamp = updated.timestamp;
+            let changed = *d != before;
+            self.mark_dirty_if(changed);
             true
         } else {
             false
@@ -100,31 +729,385 @@ impl Store {
 
     // --- Weight CRUD ---
 
-    pub fn add_weight(&mut self, mut weight: Weight) -> u32 {
-        weight.id = self.next_id;
-        self.next_id += 1;
-        let id = weight.id;
+    pub fn add_weight(&mut self, mut weight: Weight) -> Result<u64, TrackerError> {
+        let id = self.allocate_id()?;
+        weight.id = id;
         self.weights.push(weight);
-        id
+        Ok(id)
     }
 
-    pub fn delete_weight(&mut self, id: u32) -> bool {
+    pub fn delete_weight(&mut self, id: u64) -> bool {
         let before = self.weights.len();
         self.weights.retain(|w| w.id != id);
-        self.weights.len() < before
+        let removed = self.weights.len() < before;
+        self.mark_dirty_if(removed);
+        removed
     }
 
-    pub fn update_weight(&mut self, id: u32, updated: Weight) -> bool {
+    pub fn update_weight(&mut self, id: u64, updated: Weight) -> bool {
         if let Some(w) = self.weights.iter_mut().find(|w| w.id == id) {
+            let before = w.clone();
             w.weight_kg = updated.weight_kg;
             w.notes = updated.notes;
             w.timestamp = updated.timestamp;
+            let changed = *w != before;
+            self.mark_dirty_if(changed);
             true
         } else {
             false
         }
     }
 
+    // --- Lookup by id ---
+
+    pub fn get_feeding(&self, id: u64) -> Option<&Feeding> {
+        self.feedings.iter().find(|f| f.id == id)
+    }
+
+    pub fn get_dejection(&self, id: u64) -> Option<&Dejection> {
+        self.dejections.iter().find(|d| d.id == id)
+    }
+
+    pub fn get_weight(&self, id: u64) -> Option<&Weight> {
+        self.weights.iter().find(|w| w.id == id)
+    }
+
+    /// Ids come from a single shared counter, so at most one collection can match.
+    pub fn get_entry(&self, id: u64) -> Option<TimelineEntry> {
+        self.get_feeding(id)
+            .map(TimelineEntry::from_feeding)
+            .or_else(|| self.get_dejection(id).map(TimelineEntry::from_dejection))
+            .or_else(|| self.get_weight(id).map(TimelineEntry::from_weight))
+    }
+
+    // --- Append note ---
+
+    /// Adds `text` to the notes of whichever record has this id, after a `"; "`
+    /// when notes already exist. Blank text leaves the notes alone. Returns false
+    /// when no record has this id.
+    pub fn append_note(&mut self, id: u64, text: &str) -> bool {
+        let notes = if let Some(f) = self.feedings.iter_mut().find(|f| f.id == id) {
+            &mut f.notes
+        } else if let Some(d) = self.dejections.iter_mut().find(|d| d.id == id) {
+            &mut d.notes
+        } else if let Some(w) = self.weights.iter_mut().find(|w| w.id == id) {
+            &mut w.notes
+        } else {
+            return false;
+        };
+        let text = text.trim();
+        if !text.is_empty() {
+            *notes = Some(match notes.take() {
+                Some(existing) => format!("{}; {}", existing, text),
+                None => text.to_string(),
+            });
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        true
+    }
+
+    /// Most recent feeding, dejection and weight by timestamp.
+    pub fn latest_of_each(&self, baby_name: Option<&str>) -> (Option<Feeding>, Option<Dejection>, Option<Weight>) {
+        let name_matches = |n: &str| self.name_matches(baby_name, n);
+        let feeding = self
+            .feedings
+            .iter()
+            .filter(|f| name_matches(&f.baby_name))
+            .max_by_key(|f| f.timestamp)
+            .cloned();
+        let dejection = self
+            .dejections
+            .iter()
+            .filter(|d| name_matches(&d.baby_name))
+            .max_by_key(|d| d.timestamp)
+            .cloned();
+        let weight = self
+            .weights
+            .iter()
+            .filter(|w| name_matches(&w.baby_name))
+            .max_by_key(|w| w.timestamp)
+            .cloned();
+        (feeding, dejection, weight)
+    }
+
+    /// The latest event of any kind strictly before `before`. Ties on the
+    /// timestamp go to the one that sorts last in the timeline.
+    pub fn last_entry_before(&self, baby_name: Option<&str>, before: NaiveDateTime) -> Option<TimelineEntry> {
+        self.iter_entries()
+            .filter(|e| e.timestamp < before && self.name_matches(baby_name, &e.baby_name))
+            .max_by_key(TimelineEntry::sort_key)
+    }
+
+    // --- Duplicate (clone with a new id, optionally retimed) ---
+
+    pub fn duplicate_feeding(&mut self, id: u64, new_timestamp: Option<NaiveDateTime>) -> Result<Option<u64>, TrackerError> {
+        let Some(original) = self.get_feeding(id) else {
+            return Ok(None);
+        };
+        let mut copy = original.clone();
+        if let Some(ts) = new_timestamp {
+            copy.timestamp = ts;
+        }
+        self.add_feeding(copy).map(Some)
+    }
+
+    pub fn duplicate_dejection(&mut self, id: u64, new_timestamp: Option<NaiveDateTime>) -> Result<Option<u64>, TrackerError> {
+        let Some(original) = self.get_dejection(id) else {
+            return Ok(None);
+        };
+        let mut copy = original.clone();
+        if let Some(ts) = new_timestamp {
+            copy.timestamp = ts;
+        }
+        self.add_dejection(copy).map(Some)
+    }
+
+    pub fn duplicate_weight(&mut self, id: u64, new_timestamp: Option<NaiveDateTime>) -> Result<Option<u64>, TrackerError> {
+        let Some(original) = self.get_weight(id) else {
+            return Ok(None);
+        };
+        let mut copy = original.clone();
+        if let Some(ts) = new_timestamp {
+            copy.timestamp = ts;
+        }
+        self.add_weight(copy).map(Some)
+    }
+
+    /// Copies every feeding logged on `from` to the same time of day on `to`,
+    /// in time order, returning the new ids. Dejections and weights are left
+    /// alone since they do not follow a schedule.
+    pub fn clone_day(&mut self, baby_name: Option<&str>, from: NaiveDate, to: NaiveDate) -> Result<Vec<u64>, TrackerError> {
+        let mut copies: Vec<Feeding> = self
+            .feedings
+            .iter()
+            .filter(|f| f.timestamp.date() == from && self.name_matches(baby_name, &f.baby_name))
+            .cloned()
+            .collect();
+        copies.sort_by_key(|f| f.timestamp);
+        copies
+            .into_iter()
+            .map(|mut f| {
+                f.timestamp = to.and_time(f.timestamp.time());
+                self.add_feeding(f)
+            })
+            .collect()
+    }
+
+    // --- Merge ---
+
+    /// Appends `other`'s records under fresh ids from this store's counter,
+    /// skipping any that exactly match an existing record (same baby, type,
+    /// amount and timestamp). Records are copied as they are, without this
+    /// store's volume rounding. Settings are kept from `self`, and `other`'s baby
+    /// metadata only fills in babies `self` has none for. Returns how many
+    /// records were added.
+    pub fn merge(&mut self, other: Store) -> Result<usize, TrackerError> {
+        let mut added = 0;
+        for mut f in other.feedings {
+            if !self.feedings.iter().any(|e| same_feeding(e, &f)) {
+                f.id = self.allocate_id()?;
+                self.feedings.push(f);
+                added += 1;
+            }
+        }
+        for d in other.dejections {
+            if !self.dejections.iter().any(|e| same_dejection(e, &d)) {
+                self.add_dejection(d)?;
+                added += 1;
+            }
+        }
+        for w in other.weights {
+            if !self.weights.iter().any(|e| same_weight(e, &w)) {
+                self.add_weight(w)?;
+                added += 1;
+            }
+        }
+        for (name, meta) in other.babies {
+            if let std::collections::btree_map::Entry::Vacant(slot) = self.babies.entry(name) {
+                slot.insert(meta);
+                self.dirty.store(true, Ordering::Relaxed);
+            }
+        }
+        Ok(added)
+    }
+
+    // --- Diff ---
+
+    /// Records found in only one of the two stores, compared by content the
+    /// same way `merge` does, so ids are ignored. Each side keeps its own ids.
+    pub fn diff(&self, other: &Store) -> StoreDiff {
+        fn one_side(a: &Store, b: &Store) -> Vec<TimelineEntry> {
+            let feedings = a
+                .feedings
+                .iter()
+                .filter(|f| !b.feedings.iter().any(|e| same_feeding(e, f)))
+                .map(TimelineEntry::from_feeding);
+            let dejections = a
+                .dejections
+                .iter()
+                .filter(|d| !b.dejections.iter().any(|e| same_dejection(e, d)))
+                .map(TimelineEntry::from_dejection);
+            let weights = a
+                .weights
+                .iter()
+                .filter(|w| !b.weights.iter().any(|e| same_weight(e, w)))
+                .map(TimelineEntry::from_weight);
+            let mut entries: Vec<TimelineEntry> = feedings.chain(dejections).chain(weights).collect();
+            sort_timeline(&mut entries);
+            entries
+        }
+        StoreDiff {
+            only_in_self: one_side(self, other),
+            only_in_other: one_side(other, self),
+        }
+    }
+
+    // --- Data quality ---
+
+    /// Human-readable warnings about suspicious records: feedings flagged by
+    /// `Feeding::consistency_warnings` or missing both amount and duration,
+    /// implausible weight drops between consecutive weigh-ins of the same baby,
+    /// and events dated after `now`.
+    pub fn validate(&self, now: NaiveDateTime) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let fmt = |ts: NaiveDateTime| self.format_timestamp(ts);
+
+        for f in &self.feedings {
+            let mismatches = f.consistency_warnings();
+            for m in &mismatches {
+                warnings.push(format!("Feeding #{} ({}) is {}", f.id, fmt(f.timestamp), m));
+            }
+            if mismatches.is_empty() && f.amount_ml.is_none() && f.duration_minutes.is_none() {
+                warnings.push(format!("Feeding #{} ({}) has neither amount nor duration", f.id, fmt(f.timestamp)));
+            }
+        }
+
+        let mut weights: Vec<&Weight> = self.weights.iter().collect();
+        weights.sort_by(|a, b| a.baby_name.cmp(&b.baby_name).then(a.timestamp.cmp(&b.timestamp)));
+        for pair in weights.windows(2) {
+            let (prev, next) = (pair[0], pair[1]);
+            if prev.baby_name == next.baby_name && next.weight_kg < prev.weight_kg * (1.0 - MAX_PLAUSIBLE_WEIGHT_DROP) {
+                warnings.push(format!(
+                    "Weight #{} ({}) dropped from {} kg to {} kg since the previous weigh-in",
+                    next.id,
+                    fmt(next.timestamp),
+                    prev.weight_kg,
+                    next.weight_kg
+                ));
+            }
+        }
+
+        for entry in self.iter_entries().filter(|e| e.timestamp > now) {
+            warnings.push(format!("{} #{} is dated in the future ({})", capitalize(entry.kind), entry.id, fmt(entry.timestamp)));
+        }
+
+        warnings
+    }
+
+    /// Feedings missing the field their type calls for (see
+    /// `Feeding::is_incomplete`), in stored order, for "complete this entry"
+    /// prompts.
+    pub fn incomplete_feedings(&self, baby_name: Option<&str>) -> Vec<&Feeding> {
+        self.feedings
+            .iter()
+            .filter(|f| self.name_matches(baby_name, &f.baby_name) && f.is_incomplete())
+            .collect()
+    }
+
+    // --- Iteration ---
+
+    /// Every record as a `TimelineEntry`, lazily: feedings, then dejections, then
+    /// weights, each in the order they were added.
+    pub fn iter_entries(&self) -> impl Iterator<Item = TimelineEntry> + '_ {
+        self.feedings
+            .iter()
+            .map(TimelineEntry::from_feeding)
+            .chain(self.dejections.iter().map(TimelineEntry::from_dejection))
+            .chain(self.weights.iter().map(TimelineEntry::from_weight))
+    }
+
+    /// Every record, ascending by id (i.e. creation order across all kinds), so
+    /// a sync client can pick up everything after the last id it has seen.
+    pub fn entries_by_id(&self) -> Vec<TimelineEntry> {
+        let mut entries: Vec<TimelineEntry> = self.iter_entries().collect();
+        entries.sort_by_key(|e| e.id);
+        entries
+    }
+
+    // --- Counts by kind ---
+
+    /// Number of entries of each kind in `[since, until)`. Every kind is present,
+    /// with 0 when nothing of that kind was logged.
+    pub fn counts_by_kind(
+        &self,
+        baby_name: Option<&str>,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> BTreeMap<String, u64> {
+        let mut counts: BTreeMap<String, u64> =
+            ["feeding", "dejection", "weight"].iter().map(|k| (k.to_string(), 0)).collect();
+        for e in self.iter_entries() {
+            if e.timestamp >= since && e.timestamp < until && self.name_matches(baby_name, &e.baby_name) {
+                *counts.entry(e.kind.to_string()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    // --- Tracking span ---
+
+    /// Timestamps of every event for the baby (or all babies), in no particular order.
+    fn event_timestamps<'a>(&'a self, baby_name: Option<&'a str>) -> impl Iterator<Item = NaiveDateTime> + 'a {
+        let name_matches = move |n: &str| self.name_matches(baby_name, n);
+        self.feedings
+            .iter()
+            .filter(move |f| name_matches(&f.baby_name))
+            .map(|f| f.timestamp)
+            .chain(self.dejections.iter().filter(move |d| name_matches(&d.baby_name)).map(|d| d.timestamp))
+            .chain(self.weights.iter().filter(move |w| name_matches(&w.baby_name)).map(|w| w.timestamp))
+    }
+
+    /// Earliest and latest event timestamps across feedings, dejections and weights.
+    pub fn tracking_span(&self, baby_name: Option<&str>) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        self.event_timestamps(baby_name).fold(None, |span, ts| match span {
+            None => Some((ts, ts)),
+            Some((first, last)) => Some((first.min(ts), last.max(ts))),
+        })
+    }
+
+    /// Number of calendar days touched by the tracking span, counting both ends.
+    pub fn tracking_days(&self, baby_name: Option<&str>) -> u32 {
+        self.tracking_span(baby_name)
+            .map_or(0, |(first, last)| (last.date() - first.date()).num_days() as u32 + 1)
+    }
+
+    /// Consecutive days ending at `up_to` with at least one event of any kind.
+    /// Returns 0 when nothing was logged on `up_to` itself.
+    pub fn logging_streak(&self, baby_name: Option<&str>, up_to: NaiveDate) -> u32 {
+        let days: HashSet<NaiveDate> = self.event_timestamps(baby_name).map(|ts| ts.date()).collect();
+        let mut streak = 0;
+        let mut day = up_to;
+        while days.contains(&day) {
+            streak += 1;
+            match day.pred_opt() {
+                Some(prev) => day = prev,
+                None => break,
+            }
+        }
+        streak
+    }
+
+    /// `YYYY-MM-DD` for each day in `[start, end)` with no events of any kind.
+    pub fn missing_days(&self, baby_name: Option<&str>, start: NaiveDate, end: NaiveDate) -> Vec<String> {
+        let days: HashSet<NaiveDate> = self.event_timestamps(baby_name).map(|ts| ts.date()).collect();
+        start
+            .iter_days()
+            .take_while(|day| *day < end)
+            .filter(|day| !days.contains(day))
+            .map(|day| day.format("%Y-%m-%d").to_string())
+            .collect()
+    }
+
     // --- Unified timeline ---
 
     pub fn timeline_for_day(
@@ -138,7 +1121,7 @@ impl Store {
         for f in &self.feedings {
             if f.timestamp >= day_start
                 && f.timestamp < day_end
-                && baby_name.map_or(true, |name| f.baby_name == name)
+                && self.name_matches(baby_name, &f.baby_name)
             {
                 entries.push(TimelineEntry::from_feeding(f));
             }
@@ -147,22 +1130,45 @@ impl Store {
         for d in &self.dejections {
             if d.timestamp >= day_start
                 && d.timestamp < day_end
-                && baby_name.map_or(true, |name| d.baby_name == name)
+                && self.name_matches(baby_name, &d.baby_name)
             {
-                entries.push(TimelineEntry::from_dejection(d));
+                let mut entry = TimelineEntry::from_dejection(d);
+                entry.label = self.dejection_label(&d.dejection_type).map(str::to_string);
+                entries.push(entry);
             }
         }
 
         for w in &self.weights {
             if w.timestamp >= day_start
                 && w.timestamp < day_end
-                && baby_name.map_or(true, |name| w.baby_name == name)
+                && self.name_matches(baby_name, &w.baby_name)
             {
                 entries.push(TimelineEntry::from_weight(w));
             }
         }
 
-        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        for e in &mut entries {
+            e.age_days = self.age_days(&e.baby_name, e.timestamp);
+        }
+        sort_timeline(&mut entries);
+        entries
+    }
+
+    /// Same as `timeline_for_day`, with `cumulative_ml` set on every feeding to
+    /// the ml fed so far that day (across all babies when `baby_name` is `None`).
+    /// Feedings without an amount carry the running total forward unchanged.
+    pub fn timeline_for_day_cumulative(
+        &self,
+        baby_name: Option<&str>,
+        day_start: NaiveDateTime,
+        day_end: NaiveDateTime,
+    ) -> Vec<TimelineEntry> {
+        let mut entries = self.timeline_for_day(baby_name, day_start, day_end);
+        let mut running = 0.0;
+        for e in entries.iter_mut().filter(|e| e.kind == "feeding") {
+            running += e.consumed_ml().unwrap_or(0.0);
+            e.cumulative_ml = Some(running);
+        }
         entries
     }
 
@@ -173,66 +1179,790 @@ impl Store {
         baby_name: Option<&str>,
         since: NaiveDateTime,
         until: NaiveDateTime,
+    ) -> Summary {
+        self.summary_with(baby_name, since, until, false)
+    }
+
+    /// Same as `summary`, but `by_type` and `by_type_detail` list all four
+    /// built-in feeding types, with zeros for types not fed in the window.
+    /// Custom types only appear when used.
+    pub fn summary_full(
+        &self,
+        baby_name: Option<&str>,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Summary {
+        self.summary_with(baby_name, since, until, true)
+    }
+
+    fn summary_with(
+        &self,
+        baby_name: Option<&str>,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+        include_empty_types: bool,
     ) -> Summary {
         let in_range = |ts: NaiveDateTime| ts >= since && ts < until;
 
         let filtered: Vec<&Feeding> = self
             .feedings
             .iter()
-            .filter(|f| in_range(f.timestamp) && baby_name.map_or(true, |name| f.baby_name == name))
+            .filter(|f| in_range(f.timestamp) && self.name_matches(baby_name, &f.baby_name))
             .collect();
 
         let total_feedings = filtered.len() as u64;
-        let total_ml: f64 = filtered.iter().filter_map(|f| f.amount_ml).sum();
+        let total_ml: f64 = filtered.iter().filter_map(|f| f.consumed_ml()).sum();
+        let total_wasted_ml: f64 = filtered.iter().filter_map(|f| f.leftover_ml).sum();
         let total_minutes: u32 = filtered.iter().filter_map(|f| f.duration_minutes).sum();
 
+        // Built-in types in a fixed order, then any custom ones by name.
+        let custom: BTreeSet<&str> = filtered
+            .iter()
+            .filter_map(|f| match &f.feeding_type {
+                FeedingType::Other(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+        let types = FeedingType::all()
+            .iter()
+            .cloned()
+            .chain(custom.into_iter().map(|name| FeedingType::Other(name.to_string())));
+
         let mut by_type: Vec<(FeedingType, u64)> = Vec::new();
-        for ft in &[
-            FeedingType::BreastLeft,
-            FeedingType::BreastRight,
-            FeedingType::Bottle,
-            FeedingType::Solid,
-        ] {
-            let count = filtered.iter().filter(|f| f.feeding_type == *ft).count() as u64;
-            if count > 0 {
+        let mut by_type_detail: Vec<(FeedingType, u64, f64, u32)> = Vec::new();
+        for ft in types {
+            let of_type: Vec<&&Feeding> = filtered.iter().filter(|f| f.feeding_type == ft).collect();
+            let count = of_type.len() as u64;
+            if count > 0 || include_empty_types {
+                let ml: f64 = of_type.iter().filter_map(|f| f.consumed_ml()).sum();
+                let minutes: u32 = of_type.iter().filter_map(|f| f.duration_minutes).sum();
                 by_type.push((ft.clone(), count));
+                by_type_detail.push((ft, count, ml, minutes));
             }
         }
 
         let dejection_filtered: Vec<&Dejection> = self
             .dejections
             .iter()
-            .filter(|d| in_range(d.timestamp) && baby_name.map_or(true, |name| d.baby_name == name))
+            .filter(|d| in_range(d.timestamp) && self.name_matches(baby_name, &d.baby_name))
             .collect();
 
-        let total_urine = dejection_filtered
-            .iter()
-            .filter(|d| d.dejection_type == DejectionType::Urine)
-            .count() as u64;
-        let total_poop = dejection_filtered
-            .iter()
-            .filter(|d| d.dejection_type == DejectionType::Poop)
-            .count() as u64;
+        let [total_urine, total_poop] = DejectionType::all()
+            .each_ref()
+            .map(|dt| dejection_filtered.iter().filter(|d| d.dejection_type == *dt).count() as u64);
+        let rash_count = dejection_filtered.iter().filter(|d| d.rash).count() as u64;
 
         let latest_weight_kg = self
             .weights
             .iter()
-            .filter(|w| in_range(w.timestamp) && baby_name.map_or(true, |name| w.baby_name == name))
+            .filter(|w| in_range(w.timestamp) && self.name_matches(baby_name, &w.baby_name))
             .max_by(|a, b| a.timestamp.cmp(&b.timestamp))
             .map(|w| w.weight_kg);
 
+        let day_events: Vec<NaiveDateTime> = self.event_timestamps(baby_name).filter(|ts| in_range(*ts)).collect();
+        let first_event = day_events.iter().min().copied();
+        let last_event = day_events.iter().max().copied();
+
         Summary {
             total_feedings,
             total_ml,
+            total_wasted_ml,
             total_minutes,
             by_type,
+            by_type_detail,
             total_urine,
             total_poop,
+            rash_count,
             latest_weight_kg,
+            first_event,
+            last_event,
         }
     }
 
-    // --- Report (per-day aggregates for a date range) ---
+    /// One summary per baby with at least one event in the window, by name.
+    pub fn summary_per_baby(&self, since: NaiveDateTime, until: NaiveDateTime) -> Vec<(String, Summary)> {
+        let in_range = |ts: NaiveDateTime| ts >= since && ts < until;
+        let names: BTreeSet<&str> = self
+            .feedings
+            .iter()
+            .filter(|f| in_range(f.timestamp))
+            .map(|f| f.baby_name.as_str())
+            .chain(self.dejections.iter().filter(|d| in_range(d.timestamp)).map(|d| d.baby_name.as_str()))
+            .chain(self.weights.iter().filter(|w| in_range(w.timestamp)).map(|w| w.baby_name.as_str()))
+            .collect();
+        // Under case-insensitive matching, spellings of one name share a summary.
+        let mut seen = HashSet::new();
+        names
+            .into_iter()
+            .filter(|name| match self.settings.name_matching {
+                NameMatching::Exact => true,
+                NameMatching::CaseInsensitive => seen.insert(name.to_lowercase()),
+            })
+            .map(|name| (name.to_string(), self.summary(Some(name), since, until)))
+            .collect()
+    }
+
+    // --- Today at a glance ---
+
+    /// Counts for the calendar day of `now`, plus the last feeding and latest
+    /// weight at or before `now`.
+    pub fn glance(&self, baby_name: Option<&str>, now: NaiveDateTime) -> Glance {
+        let day_start = now.date().and_time(chrono::NaiveTime::MIN);
+        let today = self.summary(baby_name, day_start, day_start + chrono::Duration::days(1));
+        let last_feeding = self
+            .feedings
+            .iter()
+            .filter(|f| f.timestamp <= now && self.name_matches(baby_name, &f.baby_name))
+            .map(|f| f.timestamp)
+            .max();
+        let latest_weight_kg = self
+            .weights
+            .iter()
+            .filter(|w| w.timestamp <= now && self.name_matches(baby_name, &w.baby_name))
+            .max_by_key(|w| w.timestamp)
+            .map(|w| w.weight_kg);
+        Glance {
+            feedings_today: today.total_feedings,
+            ml_today: today.total_ml,
+            wet_today: today.total_urine,
+            dirty_today: today.total_poop,
+            last_feeding,
+            minutes_since_last_feeding: last_feeding.map(|ts| (now - ts).num_minutes()),
+            latest_weight_kg,
+        }
+    }
+
+    // --- Time since ---
+
+    /// Time from the latest diaper at or before `now` to `now`.
+    pub fn time_since_last_dejection(&self, baby_name: Option<&str>, now: NaiveDateTime) -> Option<chrono::Duration> {
+        self.dejections
+            .iter()
+            .filter(|d| d.timestamp <= now && self.name_matches(baby_name, &d.baby_name))
+            .map(|d| now - d.timestamp)
+            .min()
+    }
+
+    /// Time from the latest weigh-in at or before `now` to `now`.
+    pub fn time_since_last_weight(&self, baby_name: Option<&str>, now: NaiveDateTime) -> Option<chrono::Duration> {
+        self.weights
+            .iter()
+            .filter(|w| w.timestamp <= now && self.name_matches(baby_name, &w.baby_name))
+            .map(|w| now - w.timestamp)
+            .min()
+    }
+
+    // --- Dashboard ---
+
+    /// The full summary for the day of `now`, with the glance's last-feeding and
+    /// weight figures and the predicted next feeding, in one call.
+    pub fn dashboard(&self, baby_name: Option<&str>, now: NaiveDateTime) -> Dashboard {
+        let day_start = now.date().and_time(chrono::NaiveTime::MIN);
+        let glance = self.glance(baby_name, now);
+        Dashboard {
+            summary: self.summary(baby_name, day_start, day_start + chrono::Duration::days(1)),
+            last_feeding: glance.last_feeding,
+            minutes_since_last_feeding: glance.minutes_since_last_feeding,
+            next_feeding: self.predict_next_feeding(baby_name, now),
+            latest_weight_kg: glance.latest_weight_kg,
+        }
+    }
+
+    // --- Lifetime totals ---
+
+    /// All-time counts and volumes, with no date bounds.
+    pub fn lifetime_totals(&self, baby_name: Option<&str>) -> LifetimeTotals {
+        let mut totals = LifetimeTotals::default();
+        for f in self.feedings.iter().filter(|f| self.name_matches(baby_name, &f.baby_name)) {
+            totals.total_feedings += 1;
+            totals.total_ml += f.consumed_ml().unwrap_or(0.0);
+            if matches!(f.feeding_type, FeedingType::BreastLeft | FeedingType::BreastRight) {
+                totals.nursing_minutes += u64::from(f.duration_minutes.unwrap_or(0));
+            }
+        }
+        for d in self.dejections.iter().filter(|d| self.name_matches(baby_name, &d.baby_name)) {
+            match d.dejection_type {
+                DejectionType::Urine => totals.total_urine += 1,
+                DejectionType::Poop => totals.total_poop += 1,
+            }
+        }
+        totals.weigh_ins = self.weights.iter().filter(|w| self.name_matches(baby_name, &w.baby_name)).count() as u64;
+        totals
+    }
+
+    // --- Breast balance ---
+
+    /// Total (left, right) nursing minutes in the window. Only breast feedings
+    /// with a recorded duration contribute.
+    pub fn breast_balance(
+        &self,
+        baby_name: Option<&str>,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> (u32, u32) {
+        let mut left = 0;
+        let mut right = 0;
+        let in_range = self
+            .feedings
+            .iter()
+            .filter(|f| f.timestamp >= since && f.timestamp < until && self.name_matches(baby_name, &f.baby_name));
+        for f in in_range {
+            match (&f.feeding_type, f.duration_minutes) {
+                (FeedingType::BreastLeft, Some(m)) => left += m,
+                (FeedingType::BreastRight, Some(m)) => right += m,
+                _ => {}
+            }
+        }
+        (left, right)
+    }
+
+    // --- Next breast ---
+
+    /// The side opposite the most recent breast feeding, so nursing alternates.
+    /// Bottle and solid feedings are skipped; `None` if there was no nursing yet.
+    pub fn next_breast(&self, baby_name: Option<&str>) -> Option<FeedingType> {
+        let last = self
+            .feedings
+            .iter()
+            .filter(|f| {
+                matches!(f.feeding_type, FeedingType::BreastLeft | FeedingType::BreastRight)
+                    && self.name_matches(baby_name, &f.baby_name)
+            })
+            .max_by_key(|f| f.timestamp)?;
+        match last.feeding_type {
+            FeedingType::BreastLeft => Some(FeedingType::BreastRight),
+            _ => Some(FeedingType::BreastLeft),
+        }
+    }
+
+    // --- Average daily volume ---
+
+    /// Total ml fed in `[start, end)` divided by the whole days in that range.
+    /// A range shorter than one day yields 0.0.
+    pub fn avg_daily_ml(&self, baby_name: Option<&str>, start: NaiveDateTime, end: NaiveDateTime) -> f64 {
+        let days = (end - start).num_days();
+        if days <= 0 {
+            return 0.0;
+        }
+        let total_ml: f64 = self
+            .feedings
+            .iter()
+            .filter(|f| f.timestamp >= start && f.timestamp < end && self.name_matches(baby_name, &f.baby_name))
+            .filter_map(|f| f.consumed_ml())
+            .sum();
+        total_ml / days as f64
+    }
+
+    // --- Fussiness ---
+
+    /// Mean fussiness of feedings in `[since, until)` that have one recorded.
+    pub fn avg_fussiness(&self, baby_name: Option<&str>, since: NaiveDateTime, until: NaiveDateTime) -> Option<f64> {
+        let levels: Vec<u8> = self
+            .feedings
+            .iter()
+            .filter(|f| f.timestamp >= since && f.timestamp < until && self.name_matches(baby_name, &f.baby_name))
+            .filter_map(|f| f.fussiness)
+            .collect();
+        if levels.is_empty() {
+            return None;
+        }
+        Some(levels.iter().map(|&l| f64::from(l)).sum::<f64>() / levels.len() as f64)
+    }
+
+    // --- Weight velocity ---
+
+    /// Average gain in grams per day between the earliest and latest weigh-in.
+    /// Needs two weights that are not at the same instant.
+    pub fn weight_velocity(&self, baby_name: Option<&str>) -> Option<f64> {
+        let weights = || self.weights.iter().filter(|w| self.name_matches(baby_name, &w.baby_name));
+        let earliest = weights().min_by_key(|w| w.timestamp)?;
+        let latest = weights().max_by_key(|w| w.timestamp)?;
+        let days = (latest.timestamp - earliest.timestamp).num_seconds() as f64 / 86_400.0;
+        if days <= 0.0 {
+            return None;
+        }
+        Some((latest.weight_kg - earliest.weight_kg) * 1000.0 / days)
+    }
+
+    // --- Weight series ---
+
+    /// `(YYYY-MM-DD, kg)` per day in `[since, until)` with a weigh-in, keeping
+    /// the day's latest one, ascending by date. For sparklines.
+    pub fn weight_series(&self, baby_name: Option<&str>, since: NaiveDateTime, until: NaiveDateTime) -> Vec<(String, f64)> {
+        let mut by_day: BTreeMap<NaiveDate, &Weight> = BTreeMap::new();
+        for w in self
+            .weights
+            .iter()
+            .filter(|w| w.timestamp >= since && w.timestamp < until && self.name_matches(baby_name, &w.baby_name))
+        {
+            let latest = by_day.entry(w.timestamp.date()).or_insert(w);
+            if w.timestamp >= latest.timestamp {
+                *latest = w;
+            }
+        }
+        by_day
+            .into_iter()
+            .map(|(day, w)| (day.format("%Y-%m-%d").to_string(), w.weight_kg))
+            .collect()
+    }
+
+    // --- Hourly histogram ---
+
+    /// Feedings in the window counted by the hour of day they started in.
+    pub fn feeding_hour_histogram(
+        &self,
+        baby_name: Option<&str>,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> [u64; 24] {
+        let mut buckets = [0; 24];
+        self.feedings
+            .iter()
+            .filter(|f| f.timestamp >= since && f.timestamp < until && self.name_matches(baby_name, &f.baby_name))
+            .for_each(|f| buckets[f.timestamp.hour() as usize] += 1);
+        buckets
+    }
+
+    // --- Typical day ---
+
+    /// Average ml fed in each hour of the day (0-23) over the whole days in
+    /// `[since, until)`; a day with nothing fed in an hour counts as zero. Every
+    /// hour is listed, and a range shorter than one day averages to all zeros.
+    pub fn typical_day(&self, baby_name: Option<&str>, since: NaiveDateTime, until: NaiveDateTime) -> Vec<(u32, f64)> {
+        let days = (until - since).num_days();
+        let mut totals = [0.0; 24];
+        if days > 0 {
+            self.feedings
+                .iter()
+                .filter(|f| f.timestamp >= since && f.timestamp < until && self.name_matches(baby_name, &f.baby_name))
+                .for_each(|f| totals[f.timestamp.hour() as usize] += f.consumed_ml().unwrap_or(0.0));
+        }
+        (0..24).map(|hour| (hour, totals[hour as usize] / days.max(1) as f64)).collect()
+    }
+
+    // --- Nursing duration histogram ---
+
+    /// Breast feedings with a duration, counted into `0-5`, `5-10`, `10-20` and
+    /// `20+` minute buckets (lower bound inclusive). Every bucket is listed,
+    /// including empty ones.
+    pub fn nursing_duration_buckets(
+        &self,
+        baby_name: Option<&str>,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Vec<(String, u64)> {
+        const BOUNDS: [(u32, &str); 4] = [(0, "0-5"), (5, "5-10"), (10, "10-20"), (20, "20+")];
+        let mut counts = [0u64; BOUNDS.len()];
+        self.feedings
+            .iter()
+            .filter(|f| f.timestamp >= since && f.timestamp < until && self.name_matches(baby_name, &f.baby_name))
+            .filter(|f| matches!(f.feeding_type, FeedingType::BreastLeft | FeedingType::BreastRight))
+            .filter_map(|f| f.duration_minutes)
+            .for_each(|minutes| {
+                let bucket = BOUNDS.iter().rposition(|&(low, _)| minutes >= low).unwrap_or(0);
+                counts[bucket] += 1;
+            });
+        BOUNDS.iter().zip(counts).map(|(&(_, label), n)| (label.to_string(), n)).collect()
+    }
+
+    // --- Next feeding prediction ---
+
+    /// Most recent feeding (at or before `now`) plus the median interval between
+    /// the last few feedings. The median keeps one long overnight gap from
+    /// skewing the estimate. Needs at least two feedings.
+    pub fn predict_next_feeding(&self, baby_name: Option<&str>, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        const SAMPLE: usize = 8;
+
+        let mut times: Vec<NaiveDateTime> = self
+            .feedings
+            .iter()
+            .filter(|f| f.timestamp <= now && self.name_matches(baby_name, &f.baby_name))
+            .map(|f| f.timestamp)
+            .collect();
+        if times.len() < 2 {
+            return None;
+        }
+        times.sort();
+        let recent = &times[times.len().saturating_sub(SAMPLE)..];
+
+        let mut gaps: Vec<i64> = recent.windows(2).map(|w| (w[1] - w[0]).num_seconds()).collect();
+        gaps.sort();
+        let mid = gaps.len() / 2;
+        let median = if gaps.len().is_multiple_of(2) {
+            (gaps[mid - 1] + gaps[mid]) / 2
+        } else {
+            gaps[mid]
+        };
+        Some(*recent.last()? + chrono::Duration::seconds(median))
+    }
+
+    // --- Typical bottle size ---
+
+    /// Median amount of the last 10 bottle feedings that have an amount
+    /// recorded. Needs at least three such bottles.
+    pub fn typical_bottle_ml(&self, baby_name: Option<&str>) -> Option<f64> {
+        const SAMPLE: usize = 10;
+        const MIN_SAMPLES: usize = 3;
+
+        let mut bottles: Vec<(NaiveDateTime, f64)> = self
+            .feedings
+            .iter()
+            .filter(|f| f.feeding_type == FeedingType::Bottle && self.name_matches(baby_name, &f.baby_name))
+            .filter_map(|f| f.amount_ml.map(|ml| (f.timestamp, ml)))
+            .collect();
+        if bottles.len() < MIN_SAMPLES {
+            return None;
+        }
+        bottles.sort_by_key(|(ts, _)| *ts);
+
+        let mut amounts: Vec<f64> = bottles[bottles.len().saturating_sub(SAMPLE)..]
+            .iter()
+            .map(|(_, ml)| *ml)
+            .collect();
+        amounts.sort_by(f64::total_cmp);
+        let mid = amounts.len() / 2;
+        if amounts.len().is_multiple_of(2) {
+            Some((amounts[mid - 1] + amounts[mid]) / 2.0)
+        } else {
+            Some(amounts[mid])
+        }
+    }
+
+    // --- Milk sources ---
+
+    /// Distinct feeding sources recorded for the baby, sorted.
+    pub fn sources_used(&self, baby_name: Option<&str>) -> Vec<String> {
+        let sources: BTreeSet<&str> = self
+            .feedings
+            .iter()
+            .filter(|f| self.name_matches(baby_name, &f.baby_name))
+            .filter_map(|f| f.source.as_deref())
+            .collect();
+        sources.into_iter().map(str::to_string).collect()
+    }
+
+    // --- Cluster feeding ---
+
+    /// Runs of at least `min_count` feedings where each starts within
+    /// `within_minutes` (inclusive) of the previous one, as (first, last, count).
+    pub fn cluster_windows(
+        &self,
+        baby_name: Option<&str>,
+        min_count: usize,
+        within_minutes: u32,
+    ) -> Vec<(NaiveDateTime, NaiveDateTime, usize)> {
+        let mut times: Vec<NaiveDateTime> = self
+            .feedings
+            .iter()
+            .filter(|f| self.name_matches(baby_name, &f.baby_name))
+            .map(|f| f.timestamp)
+            .collect();
+        times.sort();
+
+        let max_gap = chrono::Duration::minutes(within_minutes as i64);
+        let mut clusters = Vec::new();
+        let mut run_start = 0;
+        for i in 1..=times.len() {
+            let run_continues = i < times.len() && times[i] - times[i - 1] <= max_gap;
+            if !run_continues {
+                let count = i - run_start;
+                if count >= min_count {
+                    clusters.push((times[run_start], times[i - 1], count));
+                }
+                run_start = i;
+            }
+        }
+        clusters
+    }
+
+    // --- Nursing sessions ---
+
+    /// Breast feedings in `[since, until)` merged into sessions as (start, total
+    /// minutes). A feeding joins the current session when it starts within
+    /// `gap_minutes` (inclusive) of the previous one ending, so a left side
+    /// followed by the right counts once. Any other feeding type ends the session,
+    /// and different babies never share one. Sorted by start.
+    pub fn merged_nursing_sessions(
+        &self,
+        baby_name: Option<&str>,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+        gap_minutes: u32,
+    ) -> Vec<(NaiveDateTime, u32)> {
+        let mut feedings: Vec<&Feeding> = self
+            .feedings
+            .iter()
+            .filter(|f| f.timestamp >= since && f.timestamp < until && self.name_matches(baby_name, &f.baby_name))
+            .collect();
+        feedings.sort_by(|a, b| a.baby_name.cmp(&b.baby_name).then(a.timestamp.cmp(&b.timestamp)));
+
+        let max_gap = chrono::Duration::minutes(i64::from(gap_minutes));
+        let mut sessions = Vec::new();
+        // (baby, start, minutes so far, end of the last side)
+        let mut current: Option<(&str, NaiveDateTime, u32, NaiveDateTime)> = None;
+        for f in feedings {
+            if !matches!(f.feeding_type, FeedingType::BreastLeft | FeedingType::BreastRight) {
+                sessions.extend(current.take().map(|(_, start, minutes, _)| (start, minutes)));
+                continue;
+            }
+            let minutes = f.duration_minutes.unwrap_or(0);
+            let end = f.timestamp + chrono::Duration::minutes(i64::from(minutes));
+            match &mut current {
+                Some((baby, _, total, last_end)) if *baby == f.baby_name && f.timestamp - *last_end <= max_gap => {
+                    *total += minutes;
+                    *last_end = (*last_end).max(end);
+                }
+                _ => {
+                    sessions.extend(current.take().map(|(_, start, minutes, _)| (start, minutes)));
+                    current = Some((&f.baby_name, f.timestamp, minutes, end));
+                }
+            }
+        }
+        sessions.extend(current.map(|(_, start, minutes, _)| (start, minutes)));
+        sessions.sort();
+        sessions
+    }
+
+    // --- Diaper gaps ---
+
+    /// Minutes between consecutive dejections of any type in `[since, until)`,
+    /// in chronological order. Empty with fewer than two dejections.
+    pub fn diaper_gaps(&self, baby_name: Option<&str>, since: NaiveDateTime, until: NaiveDateTime) -> Vec<u32> {
+        let mut times: Vec<NaiveDateTime> = self
+            .dejections
+            .iter()
+            .filter(|d| d.timestamp >= since && d.timestamp < until && self.name_matches(baby_name, &d.baby_name))
+            .map(|d| d.timestamp)
+            .collect();
+        times.sort();
+        times.windows(2).map(|w| (w[1] - w[0]).num_minutes() as u32).collect()
+    }
+
+    // --- Calorie estimate ---
+
+    pub fn set_calorie_factors(&mut self, kcal_per_ml: f64, kcal_per_solid: f64) {
+        self.settings.kcal_per_ml = kcal_per_ml;
+        self.settings.kcal_per_solid = kcal_per_solid;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// A rough estimate, not a nutritional measurement. Every recorded ml on a
+    /// breast or bottle feeding counts at `kcal_per_ml` (0.67 by default, typical
+    /// for both breast milk and formula), and each solid feeding counts as a
+    /// flat `kcal_per_solid` regardless of its amount. Breast feedings with only
+    /// a duration contribute nothing, since intake can't be inferred from time.
+    /// Custom types (water, juice, ...) contribute nothing either.
+    pub fn estimated_calories(&self, baby_name: Option<&str>, since: NaiveDateTime, until: NaiveDateTime) -> f64 {
+        self.feedings
+            .iter()
+            .filter(|f| f.timestamp >= since && f.timestamp < until && self.name_matches(baby_name, &f.baby_name))
+            .map(|f| match f.feeding_type {
+                FeedingType::Solid => self.settings.kcal_per_solid,
+                FeedingType::Other(_) => 0.0,
+                _ => f.consumed_ml().unwrap_or(0.0) * self.settings.kcal_per_ml,
+            })
+            .sum()
+    }
+
+    // --- Daily goal ---
+
+    /// Target ml per day. Zero or a negative value clears the goal.
+    pub fn set_daily_goal_ml(&mut self, goal_ml: f64) {
+        self.settings.daily_goal_ml = Some(goal_ml).filter(|g| *g > 0.0);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// (ml consumed on `date`, daily goal), or `None` when no goal is set.
+    pub fn goal_progress(&self, baby_name: Option<&str>, date: NaiveDate) -> Option<(f64, f64)> {
+        let goal = self.settings.daily_goal_ml?;
+        Some((self.day_ml(baby_name, date), goal))
+    }
+
+    fn day_ml(&self, baby_name: Option<&str>, date: NaiveDate) -> f64 {
+        self.feedings
+            .iter()
+            .filter(|f| f.timestamp.date() == date && self.name_matches(baby_name, &f.baby_name))
+            .filter_map(|f| f.consumed_ml())
+            .sum()
+    }
+
+    // --- Intake per kg ---
+
+    /// Ml fed on `date` divided by the latest weight recorded on or before that
+    /// day, or `None` when no such weight exists.
+    pub fn ml_per_kg_per_day(&self, baby_name: Option<&str>, date: NaiveDate) -> Option<f64> {
+        let weight = self
+            .weights
+            .iter()
+            .filter(|w| w.timestamp.date() <= date && self.name_matches(baby_name, &w.baby_name))
+            .max_by_key(|w| w.timestamp)?;
+        Some(self.day_ml(baby_name, date) / weight.weight_kg)
+    }
+
+    // --- Volume rounding ---
+
+    /// Rounds feeding amounts to the nearest multiple of `step` ml when they are
+    /// added or updated. Exact halves go to the even multiple, so with a step of
+    /// 5, 62.5 becomes 60 and 67.5 becomes 70. A step of 0 (or anything not
+    /// positive) turns rounding off. Existing records are left as they are.
+    pub fn set_volume_rounding(&mut self, step: f64) {
+        self.settings.volume_rounding = if step.is_finite() && step > 0.0 { step } else { 0.0 };
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    fn round_volume(&self, ml: f64) -> f64 {
+        let step = self.settings.volume_rounding;
+        if step > 0.0 {
+            (ml / step).round_ties_even() * step
+        } else {
+            ml
+        }
+    }
+
+    // --- Name matching ---
+
+    pub fn set_name_matching(&mut self, mode: NameMatching) {
+        self.settings.name_matching = mode;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Applies the configured `NameMatching` to a `baby_name` filter.
+    fn name_matches(&self, baby_name: Option<&str>, stored: &str) -> bool {
+        self.settings.name_matching.matches(baby_name, stored)
+    }
+
+    // --- Locale ---
+
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.settings.locale = locale;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.settings.locale
+    }
+
+    // --- Unit system ---
+
+    pub fn set_unit_system(&mut self, units: UnitSystem) {
+        self.settings.unit_system = units;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn unit_system(&self) -> UnitSystem {
+        self.settings.unit_system
+    }
+
+    // --- Display format ---
+
+    /// strftime pattern (e.g. `%d/%m/%Y %H:%M`) used by `format_timestamp`, and
+    /// so by `validate` messages and the tracker's `summary_text`. JSON output
+    /// keeps ISO 8601.
+    /// Rejected up front if chrono cannot format a timestamp with it, such as an
+    /// unknown specifier or a zone on a naive time.
+    pub fn set_display_format(&mut self, fmt: String) -> Result<(), TrackerError> {
+        use std::fmt::Write;
+        let mut probe = String::new();
+        if fmt.trim().is_empty() || write!(probe, "{}", NaiveDateTime::default().format(&fmt)).is_err() {
+            return Err(TrackerError::InvalidDisplayFormat(fmt));
+        }
+        self.settings.display_format = fmt;
+        self.dirty.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn display_format(&self) -> &str {
+        &self.settings.display_format
+    }
+
+    pub fn format_timestamp(&self, ts: NaiveDateTime) -> String {
+        use std::fmt::Write;
+        let mut out = String::new();
+        match write!(out, "{}", ts.format(&self.settings.display_format)) {
+            Ok(()) => out,
+            // Only a hand-edited export can get here; fall back rather than panic.
+            Err(_) => ts.format(DEFAULT_DISPLAY_FORMAT).to_string(),
+        }
+    }
+
+    // --- Dejection labels ---
+
+    /// Display labels (e.g. emoji) used instead of the built-in urine/poop
+    /// wording in `summary_text` and timeline entries. A blank label restores
+    /// the default for that type.
+    pub fn set_dejection_labels(&mut self, urine: String, poop: String) {
+        let clean = |label: String| Some(label.trim().to_string()).filter(|l| !l.is_empty());
+        self.settings.urine_label = clean(urine);
+        self.settings.poop_label = clean(poop);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn dejection_label(&self, dejection_type: &DejectionType) -> Option<&str> {
+        match dejection_type {
+            DejectionType::Urine => self.settings.urine_label.as_deref(),
+            DejectionType::Poop => self.settings.poop_label.as_deref(),
+        }
+    }
+
+    // --- Custom feeding types ---
+
+    /// When on, the tracker parses unknown feeding type names with
+    /// `FeedingType::parse_lenient` instead of rejecting them. Off by default so
+    /// typos are still caught.
+    pub fn set_lenient_feeding_types(&mut self, lenient: bool) {
+        self.settings.lenient_feeding_types = lenient;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn lenient_feeding_types(&self) -> bool {
+        self.settings.lenient_feeding_types
+    }
+
+    // --- Strict timestamps ---
+
+    /// When on, the tracker rejects every timestamp that is not a full
+    /// `YYYY-MM-DDTHH:MM:SS`. Off by default.
+    pub fn set_strict_timestamps(&mut self, strict: bool) {
+        self.settings.strict_timestamps = strict;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn strict_timestamps(&self) -> bool {
+        self.settings.strict_timestamps
+    }
+
+    // --- Baby metadata ---
+
+    /// Replaces the metadata for `baby_name`, whether or not it has any events.
+    /// Under case-insensitive matching, an entry stored under another casing is
+    /// replaced too.
+    pub fn set_baby_meta(&mut self, baby_name: &str, meta: BabyMeta) {
+        let name = baby_name.trim();
+        let matching = self.settings.name_matching;
+        self.babies.retain(|stored, _| !matching.matches(Some(name), stored));
+        self.babies.insert(name.to_string(), meta);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn baby_meta(&self, baby_name: &str) -> Option<&BabyMeta> {
+        let name = baby_name.trim();
+        self.babies
+            .iter()
+            .find(|(stored, _)| self.name_matches(Some(name), stored))
+            .map(|(_, meta)| meta)
+    }
+
+    /// Age as (weeks, days) at `at`, or `None` without a birthdate or before birth.
+    pub fn age_at(&self, baby_name: &str, at: NaiveDateTime) -> Option<(u32, u32)> {
+        self.age_days(baby_name, at).map(|days| (days / 7, days % 7))
+    }
+
+    fn age_days(&self, baby_name: &str, at: NaiveDateTime) -> Option<u32> {
+        let birthdate = self.baby_meta(baby_name)?.birthdate?;
+        u32::try_from((at.date() - birthdate).num_days()).ok()
+    }
+
+    // --- Report (per-day aggregates for a date range) ---
 
     pub fn report(
         &self,
@@ -245,8 +1975,9 @@ impl Store {
         while day < end {
             let next = day + chrono::Duration::days(1);
             let date_str = day.format("%Y-%m-%d").to_string();
+            let iso = day.date().iso_week();
 
-            let name_matches = |n: &str| baby_name.map_or(true, |name| n == name);
+            let name_matches = |n: &str| self.name_matches(baby_name, n);
             let in_day = |ts: NaiveDateTime| ts >= day && ts < next;
 
             let feedings: Vec<&Feeding> = self
@@ -256,20 +1987,20 @@ impl Store {
                 .collect();
 
             let total_feedings = feedings.len() as u64;
-            let total_ml: f64 = feedings.iter().filter_map(|f| f.amount_ml).sum();
+            let total_ml: f64 = feedings.iter().filter_map(|f| f.consumed_ml()).sum();
             let total_minutes: u32 = feedings.iter().filter_map(|f| f.duration_minutes).sum();
-            let breast_left = feedings.iter().filter(|f| f.feeding_type == FeedingType::BreastLeft).count() as u64;
-            let breast_right = feedings.iter().filter(|f| f.feeding_type == FeedingType::BreastRight).count() as u64;
-            let bottle = feedings.iter().filter(|f| f.feeding_type == FeedingType::Bottle).count() as u64;
-            let solid = feedings.iter().filter(|f| f.feeding_type == FeedingType::Solid).count() as u64;
+            let [breast_left, breast_right, bottle, solid] = FeedingType::all()
+                .each_ref()
+                .map(|ft| feedings.iter().filter(|f| f.feeding_type == *ft).count() as u64);
 
             let dejections: Vec<&Dejection> = self
                 .dejections
                 .iter()
                 .filter(|d| in_day(d.timestamp) && name_matches(&d.baby_name))
                 .collect();
-            let total_urine = dejections.iter().filter(|d| d.dejection_type == DejectionType::Urine).count() as u64;
-            let total_poop = dejections.iter().filter(|d| d.dejection_type == DejectionType::Poop).count() as u64;
+            let [total_urine, total_poop] = DejectionType::all()
+                .each_ref()
+                .map(|dt| dejections.iter().filter(|d| d.dejection_type == *dt).count() as u64);
 
             let weight_kg = self
                 .weights
@@ -280,6 +2011,8 @@ impl Store {
 
             reports.push(DayReport {
                 date: date_str,
+                iso_year: iso.year(),
+                iso_week: iso.week(),
                 total_feedings,
                 total_ml,
                 total_minutes,
@@ -296,22 +2029,111 @@ impl Store {
         }
         reports
     }
+
+    /// One row per day in `[start, end)` with feed counts and volumes split into
+    /// morning, afternoon, evening and night (see the `*_START_HOUR` constants).
+    /// Buckets follow the calendar day, so a day's night holds both its early
+    /// hours and its late evening.
+    pub fn report_by_daypart(&self, baby_name: Option<&str>, start: NaiveDateTime, end: NaiveDateTime) -> Vec<DaypartReport> {
+        let mut reports = Vec::new();
+        let mut day = start;
+        while day < end {
+            let next = day + chrono::Duration::days(1);
+            let mut row = DaypartReport {
+                date: day.format("%Y-%m-%d").to_string(),
+                morning: DaypartBucket::default(),
+                afternoon: DaypartBucket::default(),
+                evening: DaypartBucket::default(),
+                night: DaypartBucket::default(),
+            };
+            for f in self
+                .feedings
+                .iter()
+                .filter(|f| f.timestamp >= day && f.timestamp < next && self.name_matches(baby_name, &f.baby_name))
+            {
+                let bucket = match f.timestamp.hour() {
+                    h if (MORNING_START_HOUR..AFTERNOON_START_HOUR).contains(&h) => &mut row.morning,
+                    h if (AFTERNOON_START_HOUR..EVENING_START_HOUR).contains(&h) => &mut row.afternoon,
+                    h if (EVENING_START_HOUR..NIGHT_START_HOUR).contains(&h) => &mut row.evening,
+                    _ => &mut row.night,
+                };
+                bucket.feedings += 1;
+                bucket.total_ml += f.consumed_ml().unwrap_or(0.0);
+            }
+            reports.push(row);
+            day = next;
+        }
+        reports
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Summary {
-    pub total_feedings: u64,
-    pub total_ml: f64,
-    pub total_minutes: u32,
-    pub by_type: Vec<(FeedingType, u64)>,
-    pub total_urine: u64,
-    pub total_poop: u64,
+// Content equality used by `merge` and `diff`: same baby, type, amount and time.
+
+fn same_feeding(a: &Feeding, b: &Feeding) -> bool {
+    a.baby_name == b.baby_name && a.feeding_type == b.feeding_type && a.amount_ml == b.amount_ml && a.timestamp == b.timestamp
+}
+
+fn same_dejection(a: &Dejection, b: &Dejection) -> bool {
+    a.baby_name == b.baby_name && a.dejection_type == b.dejection_type && a.timestamp == b.timestamp
+}
+
+fn same_weight(a: &Weight, b: &Weight) -> bool {
+    a.baby_name == b.baby_name && a.weight_kg == b.weight_kg && a.timestamp == b.timestamp
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum NdjsonLine<'a> {
+    Meta {
+        next_id: u64,
+        #[serde(default)]
+        settings: Cow<'a, Settings>,
+        #[serde(default)]
+        babies: Cow<'a, BTreeMap<String, BabyMeta>>,
+    },
+    Feeding(Cow<'a, Feeding>),
+    Dejection(Cow<'a, Dejection>),
+    Weight(Cow<'a, Weight>),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_feedings: u64,
+    /// Ml taken: amounts offered less leftovers.
+    pub total_ml: f64,
+    /// Sum of `leftover_ml`: offered but not taken.
+    #[serde(default)]
+    pub total_wasted_ml: f64,
+    pub total_minutes: u32,
+    pub by_type: Vec<(FeedingType, u64)>,
+    /// (type, count, total ml, total minutes) per feeding type present in the window.
+    #[serde(default)]
+    pub by_type_detail: Vec<(FeedingType, u64, f64, u32)>,
+    pub total_urine: u64,
+    pub total_poop: u64,
+    #[serde(default)]
+    pub rash_count: u64,
     pub latest_weight_kg: Option<f64>,
+    #[serde(default)]
+    pub first_event: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub last_event: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct DayReport {
     pub date: String,
+    /// ISO 8601 week-numbering year; differs from the calendar year around New Year.
+    pub iso_year: i32,
+    pub iso_week: u32,
     pub total_feedings: u64,
     pub total_ml: f64,
     pub total_minutes: u32,
@@ -324,11 +2146,64 @@ pub struct DayReport {
     pub weight_kg: Option<f64>,
 }
 
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct DaypartBucket {
+    pub feedings: u64,
+    pub total_ml: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DaypartReport {
+    pub date: String,
+    pub morning: DaypartBucket,
+    pub afternoon: DaypartBucket,
+    pub evening: DaypartBucket,
+    pub night: DaypartBucket,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct LifetimeTotals {
+    pub total_feedings: u64,
+    pub total_ml: f64,
+    pub nursing_minutes: u64,
+    pub total_urine: u64,
+    pub total_poop: u64,
+    pub weigh_ins: u64,
+}
+
+/// Widget-sized snapshot from `Store::glance`; missing data serializes as null.
+#[derive(Debug, Serialize)]
+pub struct Glance {
+    pub feedings_today: u64,
+    pub ml_today: f64,
+    pub wet_today: u64,
+    pub dirty_today: u64,
+    pub last_feeding: Option<NaiveDateTime>,
+    pub minutes_since_last_feeding: Option<i64>,
+    pub latest_weight_kg: Option<f64>,
+}
+
+/// Result of `Store::diff`: entries, in timeline order, with no match on the other side.
+#[derive(Debug, Serialize)]
+pub struct StoreDiff {
+    pub only_in_self: Vec<TimelineEntry>,
+    pub only_in_other: Vec<TimelineEntry>,
+}
+
+/// Everything the home screen shows, from `Store::dashboard`.
+#[derive(Debug, Serialize)]
+pub struct Dashboard {
+    pub summary: Summary,
+    pub last_feeding: Option<NaiveDateTime>,
+    pub minutes_since_last_feeding: Option<i64>,
+    pub next_feeding: Option<NaiveDateTime>,
+    pub latest_weight_kg: Option<f64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::{Dejection, DejectionType, Feeding, FeedingType, Weight};
-    use chrono::{NaiveDate, Timelike};
 
     fn ts(day: u32, h: u32, m: u32) -> NaiveDateTime {
         NaiveDate::from_ymd_opt(2026, 2, day)
@@ -338,11 +2213,11 @@ mod tests {
     }
 
     fn make_feeding(name: &str, ft: FeedingType, ml: Option<f64>, dur: Option<u32>, day: u32, h: u32) -> Feeding {
-        Feeding::new(name.to_string(), ft, ml, dur, None, ts(day, h, 0)).unwrap()
+        Feeding::new(name.to_string(), ft, ml, None, dur, None, None, None, ts(day, h, 0)).unwrap()
     }
 
     fn make_dejection(name: &str, dt: DejectionType, day: u32, h: u32) -> Dejection {
-        Dejection::new(name.to_string(), dt, None, ts(day, h, 0)).unwrap()
+        Dejection::new(name.to_string(), dt, false, None, ts(day, h, 0)).unwrap()
     }
 
     fn make_weight(name: &str, kg: f64, day: u32, h: u32) -> Weight {
@@ -358,408 +2233,2494 @@ mod tests {
     }
 
     #[test]
-    fn add_assigns_incrementing_ids() {
+    fn add_assigns_incrementing_ids() {
+        let mut store = Store::new();
+        let id1 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        let id2 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 12)).unwrap();
+        assert_eq!(id1, 1);
+        assert_eq!(id2, 2);
+    }
+
+    #[test]
+    fn list_returns_all_in_reverse_chronological() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 14)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 11)).unwrap();
+        let list = store.list_feedings(None, 100);
+        assert_eq!(list.len(), 3);
+        assert!(list[0].timestamp > list[1].timestamp);
+        assert!(list[1].timestamp > list[2].timestamp);
+    }
+
+    #[test]
+    fn list_filters_by_baby_name() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 10)).unwrap();
+        assert_eq!(store.list_feedings(Some("Emma"), 100).len(), 2);
+        assert_eq!(store.list_feedings(Some("Noah"), 100).len(), 1);
+    }
+
+    #[test]
+    fn list_respects_limit() {
+        let mut store = Store::new();
+        for h in 0..10 {
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, h)).unwrap();
+        }
+        assert_eq!(store.list_feedings(None, 3).len(), 3);
+    }
+
+    #[test]
+    fn is_duplicate_feeding_within_window() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+
+        let same = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(120.0), None, None, None, None, None, ts(15, 8, 1)).unwrap();
+        assert!(store.is_duplicate_feeding(&same, 60));
+        assert!(!store.is_duplicate_feeding(&same, 30));
+
+        let other_amount = make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 8);
+        let other_type = make_feeding("Emma", FeedingType::Solid, Some(120.0), None, 15, 8);
+        let other_baby = make_feeding("Noah", FeedingType::Bottle, Some(120.0), None, 15, 8);
+        assert!(!store.is_duplicate_feeding(&other_amount, 60));
+        assert!(!store.is_duplicate_feeding(&other_type, 60));
+        assert!(!store.is_duplicate_feeding(&other_baby, 60));
+    }
+
+    #[test]
+    fn delete_feeding_existing() {
+        let mut store = Store::new();
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        assert!(store.delete_feeding(id));
+        assert_eq!(store.list_feedings(None, 100).len(), 0);
+    }
+
+    #[test]
+    fn delete_feeding_nonexistent() {
+        let mut store = Store::new();
+        assert!(!store.delete_feeding(999));
+    }
+
+    #[test]
+    fn delete_feeding_only_removes_target() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        let id2 = store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 12)).unwrap();
+        store.delete_feeding(id2);
+        assert_eq!(store.list_feedings(None, 100).len(), 2);
+    }
+
+    // --- Update feeding ---
+
+    #[test]
+    fn update_feeding_changes_fields() {
+        let mut store = Store::new();
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8)).unwrap();
+        let updated = Feeding::new("Emma".to_string(), FeedingType::Solid, Some(200.0), None, Some(10), Some("Edited".to_string()), None, None, ts(15, 9, 0)).unwrap();
+        assert!(store.update_feeding(id, updated));
+        let list = store.list_feedings(None, 100);
+        assert_eq!(list[0].feeding_type, FeedingType::Solid);
+        assert_eq!(list[0].amount_ml, Some(200.0));
+        assert_eq!(list[0].duration_minutes, Some(10));
+        assert_eq!(list[0].notes, Some("Edited".to_string()));
+        assert_eq!(list[0].timestamp.hour(), 9);
+    }
+
+    #[test]
+    fn update_feeding_nonexistent_returns_false() {
+        let mut store = Store::new();
+        let f = make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8);
+        assert!(!store.update_feeding(999, f));
+    }
+
+    #[test]
+    fn update_feeding_preserves_id_and_name() {
+        let mut store = Store::new();
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        let updated = make_feeding("Someone", FeedingType::Solid, None, None, 15, 10);
+        store.update_feeding(id, updated);
+        let list = store.list_feedings(None, 100);
+        assert_eq!(list[0].id, id);
+        assert_eq!(list[0].baby_name, "Emma");
+    }
+
+    #[test]
+    fn update_feeding_reporting_detects_no_change() {
+        let mut store = Store::new();
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.to_json();
+
+        let same = make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8);
+        assert_eq!(store.update_feeding_reporting(id, same), Some(false));
+        assert!(!store.is_dirty());
+
+        let changed = make_feeding("Emma", FeedingType::Bottle, Some(150.0), None, 15, 8);
+        assert_eq!(store.update_feeding_reporting(id, changed), Some(true));
+        assert!(store.is_dirty());
+        assert_eq!(store.get_feeding(id).unwrap().amount_ml, Some(150.0));
+
+        let missing = make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8);
+        assert_eq!(store.update_feeding_reporting(999, missing), None);
+    }
+
+    #[test]
+    fn patch_feeding_changes_only_given_fields() {
+        let mut store = Store::new();
+        let f = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(120.0), None, Some(15), Some("Fussy".to_string()), None, None, ts(15, 8, 0)).unwrap();
+        let id = store.add_feeding(f).unwrap();
+
+        let patch = FeedingPatch {
+            timestamp: Some(ts(15, 8, 30)),
+            ..Default::default()
+        };
+        assert!(store.patch_feeding(id, patch).unwrap());
+        let f = store.get_feeding(id).unwrap();
+        assert_eq!(f.timestamp, ts(15, 8, 30));
+        assert_eq!(f.feeding_type, FeedingType::Bottle);
+        assert_eq!(f.amount_ml, Some(120.0));
+        assert_eq!(f.duration_minutes, Some(15));
+        assert_eq!(f.notes, Some("Fussy".to_string()));
+    }
+
+    #[test]
+    fn patch_feeding_blank_notes_clears() {
+        let mut store = Store::new();
+        let f = Feeding::new("Emma".to_string(), FeedingType::Bottle, None, None, None, Some("Fussy".to_string()), None, None, ts(15, 8, 0)).unwrap();
+        let id = store.add_feeding(f).unwrap();
+        let patch = FeedingPatch {
+            notes: Some(" ".to_string()),
+            ..Default::default()
+        };
+        assert!(store.patch_feeding(id, patch).unwrap());
+        assert_eq!(store.get_feeding(id).unwrap().notes, None);
+        assert!(!store.patch_feeding(999, FeedingPatch::default()).unwrap());
+    }
+
+    #[test]
+    fn patch_feeding_validates_the_merged_feeding() {
+        let mut store = Store::new();
+        let f = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(120.0), Some(20.0), None, None, None, None, ts(15, 8, 0)).unwrap();
+        let id = store.add_feeding(f).unwrap();
+
+        let lower = FeedingPatch { amount_ml: Some(10.0), ..Default::default() };
+        assert_eq!(store.patch_feeding(id, lower), Err(TrackerError::LeftoverExceedsAmount));
+        let fussy = FeedingPatch { fussiness: Some(9), ..Default::default() };
+        assert_eq!(store.patch_feeding(id, fussy), Err(TrackerError::FussinessOutOfRange(9)));
+        assert_eq!(store.get_feeding(id).unwrap().amount_ml, Some(120.0));
+
+        let patch = FeedingPatch {
+            leftover_ml: Some(5.0),
+            source: Some("donor".to_string()),
+            fussiness: Some(2),
+            ..Default::default()
+        };
+        assert!(store.patch_feeding(id, patch).unwrap());
+        let f = store.get_feeding(id).unwrap();
+        assert_eq!((f.leftover_ml, f.source.as_deref(), f.fussiness), (Some(5.0), Some("donor"), Some(2)));
+    }
+
+    // --- Dejection CRUD ---
+
+    #[test]
+    fn add_dejection_assigns_id() {
+        let mut store = Store::new();
+        let id = store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8)).unwrap();
+        assert_eq!(id, 1);
+    }
+
+    #[test]
+    fn feeding_and_dejection_share_id_counter() {
+        let mut store = Store::new();
+        let id1 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        let id2 = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        let id3 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 10)).unwrap();
+        assert_eq!(id1, 1);
+        assert_eq!(id2, 2);
+        assert_eq!(id3, 3);
+    }
+
+    #[test]
+    fn delete_dejection() {
+        let mut store = Store::new();
+        let id = store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8)).unwrap();
+        assert!(store.delete_dejection(id));
+        assert!(!store.delete_dejection(id));
+    }
+
+    #[test]
+    fn update_dejection() {
+        let mut store = Store::new();
+        let id = store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8)).unwrap();
+        let updated = Dejection::new("Emma".to_string(), DejectionType::Poop, true, Some("Note".to_string()), ts(15, 9, 0)).unwrap();
+        assert!(store.update_dejection(id, updated));
+        let timeline = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(timeline[0].subtype, "poop");
+        assert_eq!(timeline[0].notes, Some("Note".to_string()));
+        assert_eq!(timeline[0].rash, Some(true));
+    }
+
+    #[test]
+    fn update_dejection_nonexistent() {
+        let mut store = Store::new();
+        let d = make_dejection("Emma", DejectionType::Urine, 15, 8);
+        assert!(!store.update_dejection(999, d));
+    }
+
+    // --- Weight CRUD ---
+
+    #[test]
+    fn add_weight_assigns_id() {
+        let mut store = Store::new();
+        let id = store.add_weight(make_weight("Emma", 3.5, 15, 8)).unwrap();
+        assert_eq!(id, 1);
+    }
+
+    #[test]
+    fn weight_shares_id_counter() {
+        let mut store = Store::new();
+        let id1 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        let id2 = store.add_weight(make_weight("Emma", 3.5, 15, 9)).unwrap();
+        let id3 = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 10)).unwrap();
+        assert_eq!(id1, 1);
+        assert_eq!(id2, 2);
+        assert_eq!(id3, 3);
+    }
+
+    #[test]
+    fn delete_weight() {
+        let mut store = Store::new();
+        let id = store.add_weight(make_weight("Emma", 3.5, 15, 8)).unwrap();
+        assert!(store.delete_weight(id));
+        assert!(!store.delete_weight(id));
+    }
+
+    #[test]
+    fn update_weight() {
+        let mut store = Store::new();
+        let id = store.add_weight(make_weight("Emma", 3.5, 15, 8)).unwrap();
+        let updated = Weight::new("Emma".to_string(), 4.0, Some("Gaining".to_string()), ts(15, 10, 0)).unwrap();
+        assert!(store.update_weight(id, updated));
+        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(tl[0].weight_kg, Some(4.0));
+        assert_eq!(tl[0].notes, Some("Gaining".to_string()));
+    }
+
+    #[test]
+    fn update_weight_preserves_name() {
+        let mut store = Store::new();
+        let id = store.add_weight(make_weight("Emma", 3.5, 15, 8)).unwrap();
+        let updated = Weight::new("Someone".to_string(), 4.0, None, ts(15, 10, 0)).unwrap();
+        store.update_weight(id, updated);
+        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(tl[0].baby_name, "Emma");
+    }
+
+    #[test]
+    fn update_weight_nonexistent() {
+        let mut store = Store::new();
+        let w = make_weight("Emma", 3.5, 15, 8);
+        assert!(!store.update_weight(999, w));
+    }
+
+    // --- Id allocation ---
+
+    #[test]
+    fn max_id_empty_and_across_collections() {
+        let mut store = Store::new();
+        assert_eq!(store.max_id(), 0);
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 9)).unwrap();
+        let id3 = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 10)).unwrap();
+        store.delete_feeding(1);
+        assert_eq!(store.max_id(), id3);
+    }
+
+    #[test]
+    fn add_fails_once_ids_are_exhausted() {
+        let mut store = Store::new();
+        store.next_id = u64::MAX - 1;
+        let last = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        assert_eq!(last, u64::MAX - 1);
+        assert_eq!(
+            store.add_weight(make_weight("Emma", 3.5, 15, 9)),
+            Err(TrackerError::IdsExhausted)
+        );
+        assert_eq!(
+            store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 10)),
+            Err(TrackerError::IdsExhausted)
+        );
+        assert_eq!(store.iter_entries().count(), 1);
+    }
+
+    #[test]
+    fn ensure_ids_available_counts_remaining_ids() {
+        let mut store = Store::new();
+        store.next_id = u64::MAX - 2;
+        assert_eq!(store.ensure_ids_available(2), Ok(()));
+        assert_eq!(store.ensure_ids_available(3), Err(TrackerError::IdsExhausted));
+    }
+
+    #[test]
+    fn from_json_with_max_id_does_not_overflow() {
+        let json = format!(
+            r#"{{"feedings":[{{"id":{},"baby_name":"Emma","feeding_type":"bottle","amount_ml":null,"duration_minutes":null,"notes":null,"timestamp":"2026-02-15T08:00:00"}}],"next_id":1}}"#,
+            u64::MAX
+        );
+        let mut store = Store::from_json(&json).unwrap();
+        assert_eq!(
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 9)),
+            Err(TrackerError::IdsExhausted)
+        );
+    }
+
+    #[test]
+    fn reindex_makes_ids_unique_and_contiguous() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        let gone = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 11)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 12)).unwrap();
+        store.delete_dejection(gone);
+
+        store.reindex();
+        let mut ids: Vec<u64> = store
+            .timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0))
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert_eq!(store.max_id(), 4);
+        assert_eq!(store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 13)).unwrap(), 5);
+    }
+
+    #[test]
+    fn reindex_preserves_relative_order() {
+        let mut store = Store::new();
+        store.add_weight(make_weight("Emma", 3.5, 15, 8)).unwrap();
+        store.delete_weight(1);
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.6, 15, 10)).unwrap();
+
+        store.reindex();
+        assert_eq!(store.get_entry(1).unwrap().kind, "feeding");
+        assert_eq!(store.get_entry(2).unwrap().kind, "weight");
+    }
+
+    // --- Lookup by id ---
+
+    #[test]
+    fn get_by_id_per_collection() {
+        let mut store = Store::new();
+        let fid = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        let did = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        let wid = store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+
+        assert_eq!(store.get_feeding(fid).unwrap().amount_ml, Some(120.0));
+        assert_eq!(store.get_dejection(did).unwrap().dejection_type, DejectionType::Poop);
+        assert_eq!(store.get_weight(wid).unwrap().weight_kg, 3.5);
+        assert!(store.get_feeding(did).is_none());
+        assert!(store.get_weight(fid).is_none());
+    }
+
+    #[test]
+    fn get_entry_searches_all_collections() {
+        let mut store = Store::new();
+        let fid = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        let did = store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        let wid = store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+
+        assert_eq!(store.get_entry(fid).unwrap().kind, "feeding");
+        assert_eq!(store.get_entry(did).unwrap().kind, "dejection");
+        assert_eq!(store.get_entry(wid).unwrap().kind, "weight");
+        assert!(store.get_entry(999).is_none());
+    }
+
+    // --- Append note ---
+
+    #[test]
+    fn append_note_creates_or_extends_notes() {
+        let mut store = Store::new();
+        let bare = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        let mut noted = make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 11);
+        noted.notes = Some("Fussy".to_string());
+        let noted = store.add_feeding(noted).unwrap();
+        let wid = store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+
+        assert!(store.append_note(bare, "Spat up"));
+        assert_eq!(store.get_feeding(bare).unwrap().notes.as_deref(), Some("Spat up"));
+        assert!(store.append_note(noted, " burped twice "));
+        assert_eq!(store.get_feeding(noted).unwrap().notes.as_deref(), Some("Fussy; burped twice"));
+        assert!(store.append_note(wid, "After bath"));
+        assert_eq!(store.get_weight(wid).unwrap().notes.as_deref(), Some("After bath"));
+
+        assert!(store.append_note(noted, "  "));
+        assert_eq!(store.get_feeding(noted).unwrap().notes.as_deref(), Some("Fussy; burped twice"));
+        assert!(!store.append_note(999, "Lost"));
+    }
+
+    #[test]
+    fn latest_of_each_picks_most_recent_by_timestamp() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 14)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Solid, None, None, 15, 20)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 16)).unwrap();
+
+        let (feeding, dejection, weight) = store.latest_of_each(Some("Emma"));
+        assert_eq!(feeding.unwrap().amount_ml, Some(90.0));
+        assert_eq!(dejection.unwrap().dejection_type, DejectionType::Poop);
+        assert!(weight.is_none());
+
+        let (feeding, _, _) = store.latest_of_each(None);
+        assert_eq!(feeding.unwrap().baby_name, "Noah");
+    }
+
+    #[test]
+    fn last_entry_before_finds_predecessor_across_kinds() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        let poop = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        let weight = store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(90.0), None, 15, 11)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 12)).unwrap();
+
+        let prev = store.last_entry_before(Some("Emma"), ts(15, 11, 0)).unwrap();
+        assert_eq!((prev.kind, prev.id), ("weight", weight));
+        let prev = store.last_entry_before(Some("Emma"), ts(15, 10, 0)).unwrap();
+        assert_eq!((prev.kind, prev.id), ("dejection", poop));
+        assert_eq!(store.last_entry_before(None, ts(15, 11, 30)).unwrap().baby_name, "Noah");
+        assert!(store.last_entry_before(Some("Emma"), ts(15, 8, 0)).is_none());
+    }
+
+    // --- Duplicate ---
+
+    #[test]
+    fn duplicate_feeding_assigns_new_id_and_keeps_fields() {
+        let mut store = Store::new();
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        let copy_id = store.duplicate_feeding(id, None).unwrap().unwrap();
+        assert_eq!(copy_id, 2);
+        let copy = store.get_feeding(copy_id).unwrap();
+        assert_eq!(copy.baby_name, "Emma");
+        assert_eq!(copy.amount_ml, Some(120.0));
+        assert_eq!(copy.timestamp, ts(15, 8, 0));
+    }
+
+    #[test]
+    fn duplicate_feeding_overrides_timestamp() {
+        let mut store = Store::new();
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        let copy_id = store.duplicate_feeding(id, Some(ts(15, 11, 0))).unwrap().unwrap();
+        assert_eq!(store.get_feeding(copy_id).unwrap().timestamp, ts(15, 11, 0));
+        assert_eq!(store.get_feeding(id).unwrap().timestamp, ts(15, 8, 0));
+    }
+
+    #[test]
+    fn duplicate_is_independent_of_original() {
+        let mut store = Store::new();
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        let copy_id = store.duplicate_feeding(id, None).unwrap().unwrap();
+        let edited = make_feeding("Emma", FeedingType::Solid, Some(50.0), None, 15, 9);
+        assert!(store.update_feeding(copy_id, edited));
+        let original = store.get_feeding(id).unwrap();
+        assert_eq!(original.feeding_type, FeedingType::Bottle);
+        assert_eq!(original.amount_ml, Some(120.0));
+    }
+
+    #[test]
+    fn duplicate_dejection_and_weight() {
+        let mut store = Store::new();
+        let did = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 8)).unwrap();
+        let wid = store.add_weight(make_weight("Emma", 3.5, 15, 9)).unwrap();
+        let d_copy = store.duplicate_dejection(did, None).unwrap().unwrap();
+        let w_copy = store.duplicate_weight(wid, Some(ts(16, 9, 0))).unwrap().unwrap();
+        assert_eq!(store.get_dejection(d_copy).unwrap().dejection_type, DejectionType::Poop);
+        assert_eq!(store.get_weight(w_copy).unwrap().timestamp, ts(16, 9, 0));
+    }
+
+    #[test]
+    fn duplicate_nonexistent_returns_none() {
+        let mut store = Store::new();
+        assert_eq!(store.duplicate_feeding(999, None).unwrap(), None);
+        assert_eq!(store.duplicate_dejection(999, None).unwrap(), None);
+        assert_eq!(store.duplicate_weight(999, None).unwrap(), None);
+    }
+
+    #[test]
+    fn clone_day_copies_feedings_forward() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 14, 14)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 14, 7)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 14, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(60.0), None, 13, 9)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 14, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 14, 9)).unwrap();
+
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        let ids = store.clone_day(Some("Emma"), day(14), day(15)).unwrap();
+        assert_eq!(ids, vec![7, 8, 9]);
+
+        let copied: Vec<(NaiveDateTime, Option<f64>)> =
+            ids.iter().map(|&id| store.get_feeding(id).unwrap()).map(|f| (f.timestamp, f.amount_ml)).collect();
+        assert_eq!(copied, vec![(ts(15, 7, 0), Some(90.0)), (ts(15, 10, 0), None), (ts(15, 14, 0), Some(120.0))]);
+        assert_eq!(store.dejections.len(), 1);
+        assert_eq!(store.weights.len(), 1);
+        assert!(store.clone_day(Some("Liam"), day(14), day(15)).unwrap().is_empty());
+    }
+
+    // --- Merge ---
+
+    #[test]
+    fn merge_skips_exact_matches_and_reassigns_ids() {
+        let mut a = Store::new();
+        a.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        a.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+
+        let mut b = Store::new();
+        b.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        b.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        b.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 11)).unwrap();
+        b.add_weight(make_weight("Emma", 3.5, 15, 12)).unwrap();
+
+        assert_eq!(a.merge(b).unwrap(), 2);
+        assert_eq!(a.feedings.len(), 2);
+        assert_eq!(a.dejections.len(), 1);
+        assert_eq!(a.weights.len(), 1);
+
+        let ids: HashSet<u64> = a.iter_entries().map(|e| e.id).collect();
+        assert_eq!(ids.len(), 4);
+        assert_eq!(a.next_id, a.max_id() + 1);
+    }
+
+    #[test]
+    fn merge_is_idempotent() {
+        let mut a = Store::new();
+        a.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 8)).unwrap();
+        let copy = Store::from_json(&a.to_json()).unwrap();
+        assert_eq!(a.merge(copy).unwrap(), 0);
+        assert_eq!(a.feedings.len(), 1);
+        assert_eq!(a.next_id, 2);
+    }
+
+    #[test]
+    fn merge_copies_amounts_without_rounding() {
+        let mut other = Store::new();
+        let f = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(87.0), Some(12.5), None, None, None, None, ts(15, 8, 0)).unwrap();
+        other.add_feeding(f).unwrap();
+
+        let mut store = Store::new();
+        store.set_volume_rounding(10.0);
+        assert_eq!(store.merge(other).unwrap(), 1);
+        let merged = store.get_feeding(1).unwrap();
+        assert_eq!((merged.amount_ml, merged.leftover_ml), (Some(87.0), Some(12.5)));
+    }
+
+    #[test]
+    fn export_baby_json_keeps_only_that_baby() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Liam", FeedingType::Bottle, Some(90.0), None, 15, 9)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 10)).unwrap();
+        let weight_id = store.add_weight(make_weight("Emma", 3.5, 15, 11)).unwrap();
+        store.add_weight(make_weight("Liam", 4.1, 15, 12)).unwrap();
+        store.set_baby_meta("Emma", BabyMeta { color: Some("#f80".into()), birthdate: None });
+        store.set_baby_meta("Liam", BabyMeta::default());
+
+        let subset = Store::from_json(&store.export_baby_json("Emma")).unwrap();
+        assert_eq!(subset.feedings.len(), 1);
+        assert_eq!(subset.dejections.len(), 1);
+        assert_eq!(subset.weights.len(), 1);
+        assert!(subset.iter_entries().all(|e| e.baby_name == "Emma"));
+        assert_eq!(subset.babies.keys().collect::<Vec<_>>(), vec!["Emma"]);
+        assert_eq!(subset.next_id, weight_id + 1);
+
+        let nobody = Store::from_json(&store.export_baby_json("Noah")).unwrap();
+        assert_eq!(nobody.iter_entries().count(), 0);
+        assert_eq!(nobody.next_id, 1);
+    }
+
+    #[test]
+    fn export_baby_json_with_max_id_does_not_overflow() {
+        let json = format!(
+            r#"{{"feedings":[{{"id":{},"baby_name":"Emma","feeding_type":"bottle","amount_ml":null,"duration_minutes":null,"notes":null,"timestamp":"2026-02-15T08:00:00"}}],"next_id":1}}"#,
+            u64::MAX
+        );
+        let store = Store::from_json(&json).unwrap();
+        let subset = Store::from_json(&store.export_baby_json("Emma")).unwrap();
+        assert_eq!(subset.next_id, u64::MAX);
+    }
+
+    // --- Diff ---
+
+    #[test]
+    fn diff_ignores_ids_and_reports_both_sides() {
+        let mut a = Store::new();
+        a.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        a.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+
+        let mut b = Store::new();
+        b.add_weight(make_weight("Emma", 3.5, 15, 7)).unwrap();
+        b.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        b.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 11)).unwrap();
+
+        let diff = a.diff(&b);
+        assert_eq!(diff.only_in_self.len(), 1);
+        assert_eq!(diff.only_in_self[0].kind, "dejection");
+        let other: Vec<(&str, u64)> = diff.only_in_other.iter().map(|e| (e.kind, e.id)).collect();
+        assert_eq!(other, vec![("weight", 1), ("feeding", 3)]);
+
+        let same = a.diff(&a);
+        assert!(same.only_in_self.is_empty() && same.only_in_other.is_empty());
+    }
+
+    // --- Data quality ---
+
+    #[test]
+    fn validate_empty_store() {
+        assert!(Store::new().validate(ts(15, 12, 0)).is_empty());
+    }
+
+    #[test]
+    fn incomplete_bottle_needs_amount() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, Some(10), 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Liam", FeedingType::Bottle, None, None, 15, 10)).unwrap();
+        let ids: Vec<u64> = store.incomplete_feedings(Some("Emma")).iter().map(|f| f.id).collect();
+        assert_eq!(ids, vec![1]);
+        assert_eq!(store.incomplete_feedings(None).len(), 2);
+    }
+
+    #[test]
+    fn incomplete_breast_needs_duration() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, Some(60.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, Some(12), 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, None, 15, 10)).unwrap();
+        let ids: Vec<u64> = store.incomplete_feedings(None).iter().map(|f| f.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn incomplete_never_flags_solids() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, Some(50.0), Some(5), 15, 9)).unwrap();
+        assert!(store.incomplete_feedings(None).is_empty());
+    }
+
+    #[test]
+    fn validate_flags_incomplete_feedings() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, Some(10), 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, None, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, Some(10), 15, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 11)).unwrap();
+
+        let warnings = store.validate(ts(16, 0, 0));
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0], "Feeding #1 (2026-02-15 08:00) is a bottle with no amount");
+        assert_eq!(warnings[1], "Feeding #2 (2026-02-15 09:00) has neither amount nor duration");
+    }
+
+    #[test]
+    fn validate_includes_consistency_warnings() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, Some(30.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, Some(50.0), None, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, Some(10), 15, 10)).unwrap();
+
+        let warnings = store.validate(ts(16, 0, 0));
+        assert_eq!(
+            warnings,
+            vec![
+                "Feeding #1 (2026-02-15 08:00) is a breast feeding with an amount but no duration".to_string(),
+                "Feeding #2 (2026-02-15 09:00) is a solid with a volume in ml".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_flags_implausible_weight_drop_per_baby() {
+        let mut store = Store::new();
+        store.add_weight(make_weight("Emma", 4.0, 10, 8)).unwrap();
+        store.add_weight(make_weight("Noah", 3.0, 11, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.9, 12, 8)).unwrap();
+        let drop = store.add_weight(make_weight("Emma", 3.2, 14, 8)).unwrap();
+
+        let warnings = store.validate(ts(16, 0, 0));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with(&format!("Weight #{} ", drop)), "{}", warnings[0]);
+        assert!(warnings[0].contains("3.9 kg to 3.2 kg"));
+    }
+
+    #[test]
+    fn validate_flags_future_events() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 14)).unwrap();
+
+        let warnings = store.validate(ts(15, 12, 0));
+        assert_eq!(warnings, vec!["Dejection #2 is dated in the future (2026-02-15 14:00)".to_string()]);
+    }
+
+    // --- Iteration ---
+
+    #[test]
+    fn iter_entries_yields_every_record() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Solid, None, None, 16, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 14, 10)).unwrap();
+
+        let total = store.feedings.len() + store.dejections.len() + store.weights.len();
+        assert_eq!(store.iter_entries().count(), total);
+        let kinds: Vec<&str> = store.iter_entries().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec!["feeding", "feeding", "dejection", "weight"]);
+        let ml: f64 = store.iter_entries().filter_map(|e| e.amount_ml).sum();
+        assert_eq!(ml, 120.0);
+    }
+
+    #[test]
+    fn entries_by_id_follows_creation_order() {
+        let mut store = Store::new();
+        store.add_weight(make_weight("Emma", 3.5, 16, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 14, 9)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Solid, None, None, 17, 9)).unwrap();
+        store.delete_feeding(2);
+
+        let entries = store.entries_by_id();
+        let order: Vec<(u64, &str)> = entries.iter().map(|e| (e.id, e.kind)).collect();
+        assert_eq!(order, vec![(1, "weight"), (3, "dejection"), (4, "feeding")]);
+    }
+
+    // --- Counts by kind ---
+
+    #[test]
+    fn counts_by_kind_over_day() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 12)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 16, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+
+        let counts = store.counts_by_kind(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["feeding"], 2);
+        assert_eq!(counts["dejection"], 1);
+        assert_eq!(counts["weight"], 0);
+    }
+
+    // --- Tracking span ---
+
+    #[test]
+    fn tracking_span_empty() {
+        let store = Store::new();
+        assert_eq!(store.tracking_span(None), None);
+        assert_eq!(store.tracking_days(None), 0);
+    }
+
+    #[test]
+    fn tracking_span_across_all_collections() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 12, 23)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 18, 6)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 20, 8)).unwrap();
+
+        assert_eq!(store.tracking_span(Some("Emma")), Some((ts(12, 23, 0), ts(18, 6, 0))));
+        assert_eq!(store.tracking_days(Some("Emma")), 7);
+        assert_eq!(store.tracking_span(None), Some((ts(12, 23, 0), ts(20, 8, 0))));
+    }
+
+    #[test]
+    fn tracking_days_single_day() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 20)).unwrap();
+        assert_eq!(store.tracking_days(None), 1);
+    }
+
+    #[test]
+    fn logging_streak_counts_back_until_gap() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 10, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 12, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 13, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 14, 10)).unwrap();
+
+        let day = |d| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        assert_eq!(store.logging_streak(None, day(14)), 3);
+        assert_eq!(store.logging_streak(None, day(10)), 1);
+        assert_eq!(store.logging_streak(None, day(11)), 0);
+        assert_eq!(store.logging_streak(None, day(15)), 0);
+    }
+
+    #[test]
+    fn logging_streak_filters_by_name() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 14, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2026, 2, 15).unwrap();
+        assert_eq!(store.logging_streak(Some("Emma"), day), 0);
+        assert_eq!(store.logging_streak(None, day), 2);
+    }
+
+    #[test]
+    fn missing_days_finds_gap_mid_week() {
+        let mut store = Store::new();
+        for d in [9, 10, 11, 14, 15] {
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, d, 8)).unwrap();
+        }
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 12, 8)).unwrap();
+
+        let day = |d| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+        assert_eq!(store.missing_days(Some("Emma"), day(9), day(16)), vec!["2026-02-12", "2026-02-13"]);
+        assert_eq!(store.missing_days(None, day(9), day(16)), vec!["2026-02-13"]);
+        assert!(store.missing_days(Some("Emma"), day(9), day(12)).is_empty());
+        assert!(store.missing_days(None, day(12), day(12)).is_empty());
+    }
+
+    // --- Unified timeline ---
+
+    #[test]
+    fn timeline_merges_feedings_and_dejections() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, None, 15, 10)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 11)).unwrap();
+
+        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(tl.len(), 4);
+        assert_eq!(tl[0].kind, "feeding");
+        assert_eq!(tl[0].timestamp.hour(), 8);
+        assert_eq!(tl[1].kind, "dejection");
+        assert_eq!(tl[1].subtype, "urine");
+        assert_eq!(tl[2].kind, "feeding");
+        assert_eq!(tl[3].kind, "dejection");
+        assert_eq!(tl[3].subtype, "poop");
+    }
+
+    #[test]
+    fn timeline_includes_weights() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 4.2, 15, 10)).unwrap();
+
+        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(tl.len(), 2);
+        assert_eq!(tl[1].kind, "weight");
+        assert_eq!(tl[1].weight_kg, Some(4.2));
+    }
+
+    #[test]
+    fn timeline_chronological_order() {
+        let mut store = Store::new();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 14)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 6)).unwrap();
+
+        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert!(tl[0].timestamp < tl[1].timestamp);
+        assert!(tl[1].timestamp < tl[2].timestamp);
+    }
+
+    #[test]
+    fn timeline_filters_by_day() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 14, 20)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 16, 6)).unwrap();
+
+        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(tl.len(), 1);
+        assert_eq!(tl[0].kind, "dejection");
+    }
+
+    #[test]
+    fn timeline_filters_by_name() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Noah", DejectionType::Poop, 15, 9)).unwrap();
+
+        let tl = store.timeline_for_day(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(tl.len(), 1);
+        assert_eq!(tl[0].baby_name, "Emma");
+    }
+
+    #[test]
+    fn timeline_cumulative_running_sum() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 12)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 16)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(500.0), None, 14, 16)).unwrap();
+
+        let tl = store.timeline_for_day_cumulative(None, ts(15, 0, 0), ts(16, 0, 0));
+        let cumulative: Vec<Option<f64>> = tl.iter().map(|e| e.cumulative_ml).collect();
+        assert_eq!(cumulative, vec![Some(120.0), None, Some(120.0), Some(210.0), Some(310.0)]);
+        assert!(store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0)).iter().all(|e| e.cumulative_ml.is_none()));
+    }
+
+    #[test]
+    fn timeline_empty() {
+        let store = Store::new();
+        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert!(tl.is_empty());
+    }
+
+    // --- JSON persistence ---
+
+    #[test]
+    fn json_roundtrip_preserves_data() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+
+        let json = store.to_json();
+        let restored = Store::from_json(&json).unwrap();
+        let tl = restored.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(tl.len(), 3);
+        assert_eq!(tl[0].kind, "feeding");
+        assert_eq!(tl[1].kind, "dejection");
+        assert_eq!(tl[2].kind, "weight");
+    }
+
+    #[test]
+    fn to_json_is_byte_stable() {
+        let build = || {
+            let mut store = Store::new();
+            store.set_daily_goal_ml(600.0);
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+            store.add_dejection(make_dejection("Noah", DejectionType::Urine, 15, 9)).unwrap();
+            store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+            store
+        };
+        let store = build();
+        assert_eq!(store.to_json(), store.to_json());
+        assert_eq!(store.to_json(), build().to_json());
+    }
+
+    #[test]
+    fn to_json_survives_a_round_trip_unchanged() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+        let json = store.to_json();
+        assert_eq!(Store::from_json(&json).unwrap().to_json(), json);
+    }
+
+    #[test]
+    fn json_roundtrip_preserves_next_id() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+
+        let json = store.to_json();
+        let mut restored = Store::from_json(&json).unwrap();
+        let id3 = restored.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 10)).unwrap();
+        assert_eq!(id3, 3);
+    }
+
+    #[test]
+    fn json_backwards_compat_no_dejections_field() {
+        let json = r#"{"feedings":[],"next_id":1}"#;
+        let store = Store::from_json(json).unwrap();
+        assert!(store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn json_backwards_compat_no_weights_field() {
+        let json = r#"{"feedings":[],"dejections":[],"next_id":1}"#;
+        let store = Store::from_json(json).unwrap();
+        assert!(store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn from_json_clean_blob_loads_in_both_modes() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 9)).unwrap();
+        let json = store.to_json();
+
+        assert_eq!(Store::from_json(&json).unwrap().to_json(), json);
+        assert_eq!(Store::from_json_strict(&json).unwrap().to_json(), json);
+    }
+
+    // --- Integrity ---
+
+    #[test]
+    fn export_meta_counts_entries_and_loads_cleanly() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+        assert_eq!(store.entry_count(), 3);
+
+        let json = store.to_json();
+        let export: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(export["meta"]["entry_count"], 3);
+        assert_eq!(export["meta"]["content_hash"].as_str().unwrap().len(), 16);
+        assert_eq!(Store::from_json(&json).unwrap().load_warning(), None);
+        assert_eq!(Store::from_json(r#"{"feedings":[],"next_id":1}"#).unwrap().load_warning(), None);
+    }
+
+    #[test]
+    fn tampered_export_triggers_mismatch_warning() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 11)).unwrap();
+        let json = store.to_json();
+
+        let edited = json.replace(r#""amount_ml":120.0"#, r#""amount_ml":150.0"#);
+        assert_ne!(edited, json);
+        let loaded = Store::from_json(&edited).unwrap();
+        assert!(loaded.load_warning().unwrap().contains("does not match its hash"));
+        assert_eq!(loaded.get_feeding(1).unwrap().amount_ml, Some(150.0));
+        assert!(Store::from_json_strict(&edited).is_err());
+
+        let mut export: serde_json::Value = serde_json::from_str(&json).unwrap();
+        export["feedings"].as_array_mut().unwrap().pop();
+        let truncated = Store::from_json(&export.to_string()).unwrap();
+        assert!(truncated.load_warning().unwrap().contains("lists 2 entries but 1 were loaded"));
+    }
+
+    #[test]
+    fn from_json_colliding_ids() {
+        let json = r#"{"feedings":[{"id":1,"baby_name":"Emma","feeding_type":"bottle","amount_ml":null,"duration_minutes":null,"notes":null,"timestamp":"2026-02-15T08:00:00"}],
+            "dejections":[{"id":1,"baby_name":"Emma","dejection_type":"urine","notes":null,"timestamp":"2026-02-15T09:00:00"}],
+            "weights":[{"id":2,"baby_name":"Emma","weight_kg":3.5,"notes":null,"timestamp":"2026-02-15T10:00:00"}],
+            "next_id":2}"#;
+
+        let err = Store::from_json_strict(json).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid data: id 1 is used by more than one record");
+
+        let mut store = Store::from_json(json).unwrap();
+        let mut ids: Vec<u64> = store.iter_entries().map(|e| e.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 11)).unwrap(), 4);
+    }
+
+    #[test]
+    fn from_json_stale_next_id() {
+        let json = r#"{"feedings":[{"id":5,"baby_name":"Emma","feeding_type":"bottle","amount_ml":null,"duration_minutes":null,"notes":null,"timestamp":"2026-02-15T08:00:00"}],"next_id":3}"#;
+        assert!(Store::from_json_strict(json).is_err());
+        let mut store = Store::from_json(json).unwrap();
+        assert!(store.get_feeding(5).is_some());
+        assert_eq!(store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 9)).unwrap(), 6);
+    }
+
+    #[test]
+    fn from_json_invalid_returns_error() {
+        assert!(Store::from_json("not json").is_err());
+    }
+
+    // --- JSON Schema ---
+
+    #[test]
+    fn json_schema_accepts_real_export() {
+        let schema: serde_json::Value = serde_json::from_str(&Store::json_schema()).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        let mut store = Store::new();
+        store.add_feeding(Feeding::new("Emma".to_string(), FeedingType::BreastLeft, None, None, Some(15), Some("Good".to_string()), None, None, ts(15, 8, 0)).unwrap()).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.5), None, 15, 11)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Other("water".to_string()), Some(30.0), None, 15, 12)).unwrap();
+        store.add_dejection(Dejection::new("Emma".to_string(), DejectionType::Poop, true, None, ts(15, 9, 0)).unwrap()).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+        store.set_locale(Locale::Es);
+        store.set_name_matching(NameMatching::CaseInsensitive);
+        store.set_baby_meta("Emma", BabyMeta { color: Some("#ff8800".to_string()), birthdate: NaiveDate::from_ymd_opt(2026, 1, 20) });
+
+        let export: serde_json::Value = serde_json::from_str(&store.to_json()).unwrap();
+        let errors: Vec<String> = validator.iter_errors(&export).map(|e| e.to_string()).collect();
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(validator.is_valid(&serde_json::from_str(&Store::new().to_json()).unwrap()));
+    }
+
+    #[test]
+    fn json_schema_rejects_bad_records() {
+        let schema: serde_json::Value = serde_json::from_str(&Store::json_schema()).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        let bad_type = serde_json::json!({
+            "feedings": [{ "id": 1, "baby_name": "Emma", "feeding_type": "juice", "timestamp": "2026-02-15T08:00:00" }],
+            "next_id": 2
+        });
+        let bad_timestamp = serde_json::json!({
+            "feedings": [{ "id": 1, "baby_name": "Emma", "feeding_type": "bottle", "timestamp": "yesterday" }],
+            "next_id": 2
+        });
+        assert!(!validator.is_valid(&bad_type));
+        assert!(!validator.is_valid(&bad_timestamp));
+        assert!(!validator.is_valid(&serde_json::json!({ "feedings": [] })));
+    }
+
+    // --- Delete range ---
+
+    #[test]
+    fn delete_range_removes_only_in_range_for_named_baby() {
+        let mut store = Store::new();
+        let before = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 7)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+        let noah = store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 9)).unwrap();
+        let at_end = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 11)).unwrap();
+
+        assert_eq!(store.delete_range(Some("Emma"), ts(15, 8, 0), ts(15, 11, 0)), 3);
+        let remaining: Vec<u64> = store
+            .timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0))
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(remaining, vec![before, noah, at_end]);
+    }
+
+    #[test]
+    fn delete_range_without_name_removes_all_babies() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 9)).unwrap();
+        assert_eq!(store.delete_range(None, ts(15, 0, 0), ts(16, 0, 0)), 2);
+        assert_eq!(store.delete_range(None, ts(15, 0, 0), ts(16, 0, 0)), 0);
+    }
+
+    // --- Prune ---
+
+    fn store_across_days() -> Store {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 10, 8)).unwrap();
+        store.add_dejection(make_dejection("Noah", DejectionType::Poop, 11, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.4, 11, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.6, 15, 10)).unwrap();
+        store
+    }
+
+    #[test]
+    fn prune_older_than_drops_weights_too() {
+        let mut store = store_across_days();
+        assert_eq!(store.prune_older_than(ts(15, 0, 0), false), 3);
+        assert_eq!(store.iter_entries().count(), 2);
+        assert!(store.iter_entries().all(|e| e.timestamp >= ts(15, 0, 0)));
+    }
+
+    #[test]
+    fn prune_older_than_can_keep_weights() {
+        let mut store = store_across_days();
+        assert_eq!(store.prune_older_than(ts(15, 0, 0), true), 2);
+        assert_eq!(store.weights.len(), 2);
+        assert_eq!(store.feedings.len(), 1);
+        assert!(store.dejections.is_empty());
+        assert_eq!(store.prune_older_than(ts(15, 8, 0), true), 0);
+    }
+
+    // --- Delete by kind ---
+
+    fn mixed_store() -> Store {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(90.0), None, 15, 9)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 10)).unwrap();
+        store.add_dejection(make_dejection("Noah", DejectionType::Urine, 15, 11)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 12)).unwrap();
+        store.add_weight(make_weight("Noah", 3.8, 15, 13)).unwrap();
+        store
+    }
+
+    #[test]
+    fn delete_by_kind_feeding() {
+        let mut store = mixed_store();
+        assert_eq!(store.delete_by_kind("feeding", Some("Emma")), 1);
+        assert_eq!(store.delete_by_kind("feeding", None), 1);
+        assert!(store.feedings.is_empty());
+        assert_eq!(store.dejections.len() + store.weights.len(), 4);
+    }
+
+    #[test]
+    fn delete_by_kind_dejection() {
+        let mut store = mixed_store();
+        assert_eq!(store.delete_by_kind("dejection", None), 2);
+        assert!(store.dejections.is_empty());
+        assert_eq!(store.feedings.len() + store.weights.len(), 4);
+    }
+
+    #[test]
+    fn delete_by_kind_weight() {
+        let mut store = mixed_store();
+        assert_eq!(store.delete_by_kind("weight", Some("Noah")), 1);
+        assert_eq!(store.weights.len(), 1);
+        assert_eq!(store.weights[0].baby_name, "Emma");
+    }
+
+    #[test]
+    fn delete_by_kind_unknown_removes_nothing() {
+        let mut store = mixed_store();
+        assert_eq!(store.delete_by_kind("temperature", None), 0);
+        assert_eq!(store.delete_by_kind("feeding", Some("Liam")), 0);
+        assert_eq!(store.iter_entries().count(), 6);
+    }
+
+    // --- Clear ---
+
+    #[test]
+    fn clear_empties_everything_and_resets_ids() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+
+        store.clear();
+        assert_eq!(store.max_id(), 0);
+        assert_eq!(store.tracking_span(None), None);
+        assert!(store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0)).is_empty());
+        assert_eq!(store.add_weight(make_weight("Emma", 3.6, 16, 8)).unwrap(), 1);
+    }
+
+    // --- Change tracking ---
+
+    #[test]
+    fn add_sets_dirty_and_export_clears_it() {
+        let mut store = Store::new();
+        assert!(!store.is_dirty());
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        assert!(store.is_dirty());
+        let json = store.to_json();
+        assert!(!store.is_dirty());
+        assert!(!json.contains("dirty"));
+
+        assert!(!store.delete_weight(id));
+        assert!(store.append_note(id, "  "));
+        assert!(!store.is_dirty());
+        assert!(store.delete_feeding(id));
+        assert!(store.is_dirty());
+        store.to_bincode();
+        assert!(!store.is_dirty());
+    }
+
+    #[test]
+    fn store_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Store>();
+    }
+
+    #[test]
+    fn clear_marks_dirty_only_when_something_is_removed() {
+        let mut store = Store::new();
+        store.clear();
+        assert!(!store.is_dirty());
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.to_json();
+        store.clear();
+        assert!(store.is_dirty());
+        store.to_json();
+        store.clear();
+        assert!(!store.is_dirty());
+    }
+
+    #[test]
+    fn identical_updates_leave_store_clean() {
+        let mut store = Store::new();
+        let f = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        let d = store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        let w = store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+        store.to_json();
+
+        assert!(store.update_dejection(d, make_dejection("Emma", DejectionType::Urine, 15, 9)));
+        assert!(store.update_weight(w, make_weight("Emma", 3.5, 15, 10)));
+        assert!(store.patch_feeding(f, FeedingPatch { amount_ml: Some(120.0), ..Default::default() }).unwrap());
+        assert!(!store.is_dirty());
+
+        assert!(store.update_weight(w, make_weight("Emma", 3.6, 15, 10)));
+        assert!(store.is_dirty());
+        store.to_json();
+        assert!(store.update_dejection(d, make_dejection("Emma", DejectionType::Poop, 15, 9)));
+        assert!(store.is_dirty());
+        store.to_json();
+        assert!(store.patch_feeding(f, FeedingPatch { notes: Some("spat up".to_string()), ..Default::default() }).unwrap());
+        assert!(store.is_dirty());
+    }
+
+    #[test]
+    fn settings_and_loads_track_dirty() {
+        let mut store = Store::new();
+        store.set_locale(Locale::Es);
+        assert!(store.is_dirty());
+        assert!(!Store::from_json(&store.to_json()).unwrap().is_dirty());
+
+        let repaired = r#"{"feedings":[],"weights":[{"id":4,"baby_name":"Emma","weight_kg":3.5,"notes":null,"timestamp":"2026-02-15T08:00:00"}],"next_id":2}"#;
+        assert!(Store::from_json(repaired).unwrap().is_dirty());
+    }
+
+    // --- Bincode ---
+
+    #[test]
+    fn bincode_roundtrip_preserves_data() {
+        let mut store = Store::new();
+        store.add_feeding(Feeding::new("Emma".to_string(), FeedingType::BreastLeft, None, None, Some(15), Some("Good".to_string()), None, None, ts(15, 8, 0)).unwrap()).unwrap();
+        store.add_dejection(Dejection::new("Emma".to_string(), DejectionType::Poop, true, None, ts(15, 9, 0)).unwrap()).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+
+        let mut restored = Store::from_bincode(&store.to_bincode()).unwrap();
+        assert_eq!(restored.to_json(), store.to_json());
+        assert_eq!(restored.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 11)).unwrap(), 4);
+    }
+
+    #[test]
+    fn bincode_is_smaller_than_json() {
+        let mut store = Store::new();
+        for i in 0..1000 {
+            let day = 1 + (i / 40) % 28;
+            let h = i % 24;
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, day, h)).unwrap();
+        }
+        let binary = store.to_bincode();
+        let json = store.to_json();
+        assert!(binary.len() * 2 < json.len(), "binary {} vs json {}", binary.len(), json.len());
+    }
+
+    #[test]
+    fn from_bincode_invalid_returns_error() {
+        assert!(Store::from_bincode(&[1, 2, 3]).is_err());
+        assert_eq!(
+            Store::from_bincode(b"BTK").unwrap_err(),
+            TrackerError::InvalidData("not a baby tracker binary export".to_string())
+        );
+        let mut future = Store::new().to_bincode();
+        future[3] = BINCODE_VERSION + 1;
+        assert_eq!(
+            Store::from_bincode(&future).unwrap_err(),
+            TrackerError::InvalidData(format!("unsupported binary export version {} (expected {})", BINCODE_VERSION + 1, BINCODE_VERSION))
+        );
+    }
+
+    #[test]
+    fn from_bincode_repairs_ids() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 9)).unwrap();
+        store.next_id = 1;
+        let mut restored = Store::from_bincode(&store.to_bincode()).unwrap();
+        assert!(restored.is_dirty());
+        assert_eq!(restored.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap(), 3);
+
+        store.feedings[1].id = 1;
+        let restored = Store::from_bincode(&store.to_bincode()).unwrap();
+        assert!(restored.first_duplicate_id().is_none());
+    }
+
+    // --- NDJSON ---
+
+    #[test]
+    fn ndjson_one_tagged_record_per_line() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+
+        let out = store.to_ndjson();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].contains("\"kind\":\"meta\""));
+        assert!(lines[1].contains("\"kind\":\"feeding\""));
+        assert!(lines[2].contains("\"kind\":\"dejection\""));
+        assert!(lines[3].contains("\"kind\":\"weight\""));
+    }
+
+    #[test]
+    fn ndjson_roundtrip_preserves_counts_and_next_id() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 11)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        let last = store.add_weight(make_weight("Emma", 3.5, 15, 10)).unwrap();
+        store.delete_weight(last);
+
+        let mut restored = Store::from_ndjson(&store.to_ndjson()).unwrap();
+        assert_eq!(restored.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0)).len(), 3);
+        assert_eq!(restored.list_feedings(None, 100).len(), 2);
+        assert_eq!(restored.add_weight(make_weight("Emma", 3.6, 15, 12)).unwrap(), last + 1);
+    }
+
+    #[test]
+    fn ndjson_roundtrip_preserves_settings() {
+        let mut store = Store::new();
+        store.set_calorie_factors(0.7, 80.0);
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 8)).unwrap();
+
+        let restored = Store::from_ndjson(&store.to_ndjson()).unwrap();
+        assert_eq!(restored.estimated_calories(None, ts(15, 0, 0), ts(16, 0, 0)), 80.0);
+    }
+
+    // --- Baby metadata ---
+
+    #[test]
+    fn baby_meta_persists_without_events() {
+        let mut store = Store::new();
+        let meta = BabyMeta { color: Some("#3366cc".to_string()), birthdate: NaiveDate::from_ymd_opt(2026, 1, 20) };
+        store.set_baby_meta(" Noah ", meta.clone());
+
+        assert_eq!(store.baby_meta("Noah"), Some(&meta));
+        assert_eq!(store.baby_meta("Emma"), None);
+        assert_eq!(Store::from_json(&store.to_json()).unwrap().baby_meta("Noah"), Some(&meta));
+        assert_eq!(Store::from_ndjson(&store.to_ndjson()).unwrap().baby_meta("Noah"), Some(&meta));
+        store.clear();
+        assert_eq!(store.baby_meta("Noah"), Some(&meta));
+    }
+
+    #[test]
+    fn baby_meta_follows_name_matching() {
+        let mut store = Store::new();
+        store.set_name_matching(NameMatching::CaseInsensitive);
+        store.set_baby_meta("emma", BabyMeta { color: Some("red".to_string()), birthdate: None });
+        store.set_baby_meta("Emma", BabyMeta { color: Some("blue".to_string()), birthdate: None });
+        assert_eq!(store.babies.len(), 1);
+        assert_eq!(store.baby_meta("EMMA").unwrap().color.as_deref(), Some("blue"));
+    }
+
+    #[test]
+    fn age_at_counts_weeks_and_days() {
+        let mut store = Store::new();
+        assert_eq!(store.age_at("Emma", ts(15, 8, 0)), None);
+        store.set_baby_meta("Emma", BabyMeta { color: None, birthdate: NaiveDate::from_ymd_opt(2026, 1, 20) });
+
+        assert_eq!(store.age_at("Emma", ts(15, 8, 0)), Some((3, 5)));
+        assert_eq!(store.age_at("Emma", NaiveDate::from_ymd_opt(2026, 1, 20).unwrap().and_hms_opt(23, 0, 0).unwrap()), Some((0, 0)));
+        assert_eq!(store.age_at("Emma", NaiveDate::from_ymd_opt(2026, 1, 19).unwrap().and_hms_opt(23, 0, 0).unwrap()), None);
+        assert_eq!(store.age_at("Noah", ts(15, 8, 0)), None);
+    }
+
+    #[test]
+    fn timeline_carries_age_days_when_birthdate_known() {
+        let mut store = Store::new();
+        store.set_baby_meta("Emma", BabyMeta { color: None, birthdate: NaiveDate::from_ymd_opt(2026, 2, 1) });
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(90.0), None, 15, 9)).unwrap();
+
+        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(tl[0].age_days, Some(14));
+        assert_eq!(tl[1].age_days, None);
+    }
+
+    #[test]
+    fn merge_keeps_own_baby_meta() {
+        let mut a = Store::new();
+        a.set_baby_meta("Emma", BabyMeta { color: Some("red".to_string()), birthdate: None });
+        let mut b = Store::new();
+        b.set_baby_meta("Emma", BabyMeta { color: Some("blue".to_string()), birthdate: None });
+        b.set_baby_meta("Noah", BabyMeta { color: Some("green".to_string()), birthdate: None });
+        a.merge(b).unwrap();
+        assert_eq!(a.baby_meta("Emma").unwrap().color.as_deref(), Some("red"));
+        assert_eq!(a.baby_meta("Noah").unwrap().color.as_deref(), Some("green"));
+    }
+
+    #[test]
+    fn ndjson_malformed_line_reports_line_number() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        let mut data = store.to_ndjson();
+        data.push_str("{\"kind\":\"feeding\",\"oops\n");
+        let err = Store::from_ndjson(&data).unwrap_err().to_string();
+        assert!(err.contains("line 3"), "{}", err);
+    }
+
+    #[test]
+    fn ndjson_without_meta_uses_max_id() {
+        let data = r#"{"kind":"weight","id":7,"baby_name":"Emma","weight_kg":3.5,"notes":null,"timestamp":"2026-02-15T10:00:00"}"#;
+        let mut store = Store::from_ndjson(data).unwrap();
+        assert_eq!(store.add_weight(make_weight("Emma", 3.6, 15, 12)).unwrap(), 8);
+    }
+
+    // --- Summary (bounded) ---
+
+    #[test]
+    fn summary_includes_dejection_counts() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 11)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 13)).unwrap();
+
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.total_feedings, 1);
+        assert_eq!(s.total_urine, 2);
+        assert_eq!(s.total_poop, 1);
+    }
+
+    #[test]
+    fn summary_bounded_excludes_other_days() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 14, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 16, 8)).unwrap();
+
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.total_feedings, 1);
+        assert_eq!(s.total_ml, 120.0);
+    }
+
+    #[test]
+    fn summary_includes_latest_weight() {
+        let mut store = Store::new();
+        store.add_weight(make_weight("Emma", 3.5, 15, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.6, 15, 14)).unwrap();
+
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.latest_weight_kg, Some(3.6));
+    }
+
+    #[test]
+    fn summary_no_weight() {
+        let store = Store::new();
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.latest_weight_kg, None);
+    }
+
+    #[test]
+    fn summary_empty_store() {
+        let store = Store::new();
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.total_feedings, 0);
+        assert_eq!(s.total_ml, 0.0);
+        assert_eq!(s.total_minutes, 0);
+        assert_eq!(s.total_urine, 0);
+        assert_eq!(s.total_poop, 0);
+        assert_eq!(s.latest_weight_kg, None);
+        assert_eq!(s.first_event, None);
+        assert_eq!(s.last_event, None);
+    }
+
+    #[test]
+    fn summary_first_and_last_event_span_all_types() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 14, 23)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 6)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 12)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 22)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 16, 1)).unwrap();
+
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.first_event, Some(ts(15, 6, 0)));
+        assert_eq!(s.last_event, Some(ts(15, 22, 0)));
+    }
+
+    #[test]
+    fn summary_filters_by_name() {
+        let mut store = Store::new();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Noah", DejectionType::Poop, 15, 9)).unwrap();
+
+        let s = store.summary(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.total_poop, 1);
+    }
+
+    #[test]
+    fn summary_by_type_detail_splits_volume_and_minutes() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 12)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, Some(10), 15, 14)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(5), 15, 16)).unwrap();
+
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.by_type_detail.len(), 3);
+        assert_eq!(s.by_type_detail[0], (FeedingType::BreastLeft, 2, 0.0, 20));
+        assert_eq!(s.by_type_detail[1], (FeedingType::BreastRight, 1, 0.0, 10));
+        assert_eq!(s.by_type_detail[2], (FeedingType::Bottle, 2, 210.0, 0));
+        assert_eq!(s.by_type.len(), 3);
+    }
+
+    #[test]
+    fn summary_full_lists_every_type() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 12)).unwrap();
+
+        let full = store.summary_full(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(
+            full.by_type,
+            vec![
+                (FeedingType::BreastLeft, 0),
+                (FeedingType::BreastRight, 0),
+                (FeedingType::Bottle, 2),
+                (FeedingType::Solid, 0),
+            ]
+        );
+        assert_eq!(full.by_type_detail.len(), 4);
+        assert_eq!(full.by_type_detail[2], (FeedingType::Bottle, 2, 210.0, 0));
+        assert_eq!(full.total_ml, 210.0);
+
+        let compact = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(compact.by_type, vec![(FeedingType::Bottle, 2)]);
+    }
+
+    #[test]
+    fn summary_lists_custom_types_after_built_ins() {
+        let mut store = Store::new();
+        let custom = |name: &str, h| {
+            let mut f = make_feeding("Emma", FeedingType::Bottle, Some(30.0), None, 15, h);
+            f.feeding_type = FeedingType::Other(name.to_string());
+            f
+        };
+        store.add_feeding(custom("water", 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 9)).unwrap();
+        store.add_feeding(custom("juice", 10)).unwrap();
+        store.add_feeding(custom("water", 11)).unwrap();
+
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(
+            s.by_type,
+            vec![
+                (FeedingType::Bottle, 1),
+                (FeedingType::Other("juice".to_string()), 1),
+                (FeedingType::Other("water".to_string()), 2),
+            ]
+        );
+        assert_eq!(s.total_ml, 210.0);
+        assert_eq!(store.summary_full(None, ts(15, 0, 0), ts(16, 0, 0)).by_type.len(), 6);
+        assert_eq!(store.estimated_calories(None, ts(15, 0, 0), ts(16, 0, 0)), 120.0 * DEFAULT_KCAL_PER_ML);
+    }
+
+    #[test]
+    fn summary_counts_rash_diapers() {
+        let mut store = Store::new();
+        store.add_dejection(Dejection::new("Emma".to_string(), DejectionType::Poop, true, None, ts(15, 8, 0)).unwrap()).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 10)).unwrap();
+        store.add_dejection(Dejection::new("Emma".to_string(), DejectionType::Urine, true, None, ts(15, 12, 0)).unwrap()).unwrap();
+        store.add_dejection(Dejection::new("Emma".to_string(), DejectionType::Poop, true, None, ts(16, 8, 0)).unwrap()).unwrap();
+
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.rash_count, 2);
+    }
+
+    #[test]
+    fn leftover_lowers_intake_figures() {
+        let mut store = Store::new();
+        let bottle = Feeding::new("Emma".to_string(), FeedingType::Bottle, Some(150.0), Some(30.0), None, None, None, None, ts(15, 8, 0)).unwrap();
+        store.add_feeding(bottle).unwrap();
+        store.add_weight(make_weight("Emma", 4.0, 15, 7)).unwrap();
+        store.set_daily_goal_ml(600.0);
+        store.set_calorie_factors(1.0, 0.0);
+
+        let day = ts(15, 0, 0).date();
+        assert_eq!(store.summary(None, ts(15, 0, 0), ts(16, 0, 0)).total_ml, 120.0);
+        assert_eq!(store.goal_progress(None, day), Some((120.0, 600.0)));
+        assert_eq!(store.ml_per_kg_per_day(None, day), Some(30.0));
+        assert_eq!(store.estimated_calories(None, ts(15, 0, 0), ts(16, 0, 0)), 120.0);
+        assert_eq!(store.avg_daily_ml(None, ts(15, 0, 0), ts(16, 0, 0)), 120.0);
+        assert_eq!(store.typical_day(None, ts(15, 0, 0), ts(16, 0, 0))[8], (8, 120.0));
+        assert_eq!(store.lifetime_totals(None).total_ml, 120.0);
+        assert_eq!(store.get_feeding(1).unwrap().amount_ml, Some(150.0));
+    }
+
+    #[test]
+    fn summary_accumulates_wasted_ml() {
+        let mut store = Store::new();
+        for (poured, left, h) in [(120.0, Some(20.0), 8), (120.0, None, 11), (90.0, Some(15.0), 14), (90.0, Some(30.0), 26)] {
+            let mut f = make_feeding("Emma", FeedingType::Bottle, Some(poured), None, 15, 0);
+            f.leftover_ml = left;
+            f.timestamp = ts(15, 0, 0) + chrono::Duration::hours(h);
+            store.add_feeding(f).unwrap();
+        }
+
+        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(s.total_wasted_ml, 35.0);
+        assert_eq!(s.total_ml, 295.0);
+        assert_eq!(store.summary(None, ts(16, 0, 0), ts(17, 0, 0)).total_wasted_ml, 30.0);
+        assert_eq!(store.summary(Some("Noah"), ts(15, 0, 0), ts(16, 0, 0)).total_wasted_ml, 0.0);
+    }
+
+    #[test]
+    fn summary_per_baby_splits_twins() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(90.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(60.0), None, 15, 11)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        store.add_weight(make_weight("Liam", 4.0, 14, 9)).unwrap();
+
+        let per_baby = store.summary_per_baby(ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(per_baby.len(), 2);
+        assert_eq!(per_baby[0].0, "Emma");
+        assert_eq!(per_baby[0].1.total_feedings, 1);
+        assert_eq!(per_baby[0].1.total_poop, 1);
+        assert_eq!(per_baby[1].0, "Noah");
+        assert_eq!(per_baby[1].1.total_ml, 150.0);
+    }
+
+    // --- Today at a glance ---
+
+    #[test]
+    fn glance_bundles_today() {
+        let mut store = Store::new();
+        store.add_weight(make_weight("Emma", 3.4, 10, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 14, 22)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 6)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 9)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 7)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 10)).unwrap();
+        store.add_weight(make_weight("Emma", 3.6, 15, 18)).unwrap();
+
+        let g = store.glance(Some("Emma"), ts(15, 10, 45));
+        assert_eq!(g.feedings_today, 2);
+        assert_eq!(g.ml_today, 120.0);
+        assert_eq!(g.wet_today, 2);
+        assert_eq!(g.dirty_today, 1);
+        assert_eq!(g.last_feeding, Some(ts(15, 9, 0)));
+        assert_eq!(g.minutes_since_last_feeding, Some(105));
+        assert_eq!(g.latest_weight_kg, Some(3.4));
+    }
+
+    #[test]
+    fn glance_empty_serializes_nulls() {
+        let store = Store::new();
+        let json = serde_json::to_value(store.glance(None, ts(15, 10, 0))).unwrap();
+        assert_eq!(json["feedings_today"], 0);
+        assert!(json["last_feeding"].is_null());
+        assert!(json["minutes_since_last_feeding"].is_null());
+        assert!(json["latest_weight_kg"].is_null());
+    }
+
+    // --- Time since ---
+
+    #[test]
+    fn time_since_last_dejection_and_weight() {
+        let mut store = Store::new();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 11)).unwrap();
+        store.add_dejection(make_dejection("Liam", DejectionType::Urine, 15, 12)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 14)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 13, 12)).unwrap();
+
+        let now = ts(15, 12, 30);
+        assert_eq!(store.time_since_last_dejection(Some("Emma"), now), Some(chrono::Duration::minutes(90)));
+        assert_eq!(store.time_since_last_dejection(None, now), Some(chrono::Duration::minutes(30)));
+        assert_eq!(store.time_since_last_weight(Some("Emma"), now), Some(chrono::Duration::minutes(2 * 1440 + 30)));
+    }
+
+    #[test]
+    fn time_since_is_none_without_data() {
+        let mut store = Store::new();
+        let now = ts(15, 12, 0);
+        assert_eq!(store.time_since_last_dejection(None, now), None);
+        assert_eq!(store.time_since_last_weight(None, now), None);
+
+        store.add_dejection(make_dejection("Liam", DejectionType::Urine, 15, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 15, 13)).unwrap();
+        assert_eq!(store.time_since_last_dejection(Some("Emma"), now), None);
+        assert_eq!(store.time_since_last_weight(Some("Emma"), now), None);
+    }
+
+    // --- Dashboard ---
+
+    #[test]
+    fn dashboard_matches_individual_methods() {
+        let mut store = Store::new();
+        store.add_weight(make_weight("Emma", 3.6, 14, 9)).unwrap();
+        for h in [2, 5, 8] {
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, h)).unwrap();
+        }
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9)).unwrap();
+        let now = ts(15, 9, 30);
+
+        let d = store.dashboard(Some("Emma"), now);
+        let summary = store.summary(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(d.summary.total_feedings, summary.total_feedings);
+        assert_eq!(d.summary.total_ml, 300.0);
+        assert_eq!(d.summary.total_urine, 1);
+        assert_eq!(d.last_feeding, Some(ts(15, 8, 0)));
+        assert_eq!(d.minutes_since_last_feeding, Some(90));
+        assert_eq!(d.next_feeding, store.predict_next_feeding(Some("Emma"), now));
+        assert_eq!(d.next_feeding, Some(ts(15, 11, 0)));
+        assert_eq!(d.latest_weight_kg, Some(3.6));
+    }
+
+    // --- Lifetime totals ---
+
+    #[test]
+    fn lifetime_totals_span_all_days() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 1, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 10, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, Some(20), 20, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), Some(10), 28, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(500.0), None, 5, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 2, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 12, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 22, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 1, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.8, 15, 9)).unwrap();
+
+        let t = store.lifetime_totals(Some("Emma"));
+        assert_eq!(t.total_feedings, 4);
+        assert_eq!(t.total_ml, 210.0);
+        assert_eq!(t.nursing_minutes, 35);
+        assert_eq!(t.total_urine, 2);
+        assert_eq!(t.total_poop, 1);
+        assert_eq!(t.weigh_ins, 2);
+        assert_eq!(store.lifetime_totals(None).total_feedings, 5);
+    }
+
+    // --- Breast balance ---
+
+    #[test]
+    fn breast_balance_sums_sides_and_ignores_other_types() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 6)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, Some(10), 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(12), 15, 12)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, None, 15, 14)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), Some(20), 15, 16)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, Some(10), 15, 18)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(30), 16, 6)).unwrap();
+
+        assert_eq!(store.breast_balance(None, ts(15, 0, 0), ts(16, 0, 0)), (27, 10));
+    }
+
+    #[test]
+    fn breast_balance_filters_by_name() {
         let mut store = Store::new();
-        let id1 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8));
-        let id2 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 12));
-        assert_eq!(id1, 1);
-        assert_eq!(id2, 2);
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 6)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::BreastRight, None, Some(10), 15, 9)).unwrap();
+        assert_eq!(store.breast_balance(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0)), (15, 0));
     }
 
+    // --- Next breast ---
+
     #[test]
-    fn list_returns_all_in_reverse_chronological() {
+    fn next_breast_alternates_and_skips_bottles() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 14));
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 11));
-        let list = store.list_feedings(None, 100);
-        assert_eq!(list.len(), 3);
-        assert!(list[0].timestamp > list[1].timestamp);
-        assert!(list[1].timestamp > list[2].timestamp);
+        assert_eq!(store.next_breast(None), None);
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 6)).unwrap();
+        assert_eq!(store.next_breast(None), None);
+
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 11)).unwrap();
+        assert_eq!(store.next_breast(None), Some(FeedingType::BreastRight));
+
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, Some(15), 15, 13)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::BreastRight, None, Some(15), 15, 14)).unwrap();
+        assert_eq!(store.next_breast(Some("Emma")), Some(FeedingType::BreastLeft));
     }
 
+    // --- Average daily volume ---
+
     #[test]
-    fn list_filters_by_baby_name() {
+    fn avg_daily_ml_divides_by_days() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 9));
-        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 10));
-        assert_eq!(store.list_feedings(Some("Emma"), 100).len(), 2);
-        assert_eq!(store.list_feedings(Some("Noah"), 100).len(), 1);
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 14, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(80.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(60.0), None, 15, 14)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 18)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(500.0), None, 16, 8)).unwrap();
+
+        assert_eq!(store.avg_daily_ml(None, ts(14, 0, 0), ts(16, 0, 0)), 120.0);
     }
 
     #[test]
-    fn list_respects_limit() {
+    fn avg_fussiness_ignores_unrated_feedings() {
         let mut store = Store::new();
-        for h in 0..10 {
-            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, h));
+        for (level, day, h) in [(Some(1), 15, 8), (Some(4), 15, 12), (None, 15, 16), (Some(5), 16, 8)] {
+            let mut f = make_feeding("Emma", FeedingType::BreastLeft, None, Some(10), day, h);
+            f.fussiness = level;
+            store.add_feeding(f).unwrap();
         }
-        assert_eq!(store.list_feedings(None, 3).len(), 3);
+
+        assert_eq!(store.avg_fussiness(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0)), Some(2.5));
+        assert_eq!(store.avg_fussiness(None, ts(15, 0, 0), ts(17, 0, 0)), Some(10.0 / 3.0));
+        assert_eq!(store.avg_fussiness(Some("Noah"), ts(15, 0, 0), ts(17, 0, 0)), None);
+        assert_eq!(store.avg_fussiness(None, ts(15, 13, 0), ts(15, 23, 0)), None);
     }
 
     #[test]
-    fn delete_feeding_existing() {
+    fn avg_daily_ml_zero_day_range() {
         let mut store = Store::new();
-        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        assert!(store.delete_feeding(id));
-        assert_eq!(store.list_feedings(None, 100).len(), 0);
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 14, 8)).unwrap();
+        assert_eq!(store.avg_daily_ml(None, ts(14, 0, 0), ts(14, 0, 0)), 0.0);
+        assert_eq!(store.avg_daily_ml(None, ts(15, 0, 0), ts(14, 0, 0)), 0.0);
     }
 
+    // --- Weight velocity ---
+
     #[test]
-    fn delete_feeding_nonexistent() {
+    fn weight_velocity_grams_per_day() {
         let mut store = Store::new();
-        assert!(!store.delete_feeding(999));
+        store.add_weight(make_weight("Emma", 3.8, 11, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 1, 8)).unwrap();
+        store.add_weight(make_weight("Emma", 3.6, 5, 8)).unwrap();
+        store.add_weight(make_weight("Noah", 5.0, 20, 8)).unwrap();
+
+        let v = store.weight_velocity(Some("Emma")).unwrap();
+        assert!((v - 30.0).abs() < 1e-9);
     }
 
     #[test]
-    fn delete_feeding_only_removes_target() {
+    fn weight_velocity_needs_a_span() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        let id2 = store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 10));
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 12));
-        store.delete_feeding(id2);
-        assert_eq!(store.list_feedings(None, 100).len(), 2);
+        assert_eq!(store.weight_velocity(None), None);
+        store.add_weight(make_weight("Emma", 3.5, 1, 8)).unwrap();
+        assert_eq!(store.weight_velocity(None), None);
+        store.add_weight(make_weight("Emma", 3.6, 1, 8)).unwrap();
+        assert_eq!(store.weight_velocity(None), None);
     }
 
-    // --- Update feeding ---
+    // --- Weight series ---
 
     #[test]
-    fn update_feeding_changes_fields() {
+    fn weight_series_keeps_latest_per_day() {
         let mut store = Store::new();
-        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8));
-        let updated = Feeding::new("Emma".to_string(), FeedingType::Solid, Some(200.0), Some(10), Some("Edited".to_string()), ts(15, 9, 0)).unwrap();
-        assert!(store.update_feeding(id, updated));
-        let list = store.list_feedings(None, 100);
-        assert_eq!(list[0].feeding_type, FeedingType::Solid);
-        assert_eq!(list[0].amount_ml, Some(200.0));
-        assert_eq!(list[0].duration_minutes, Some(10));
-        assert_eq!(list[0].notes, Some("Edited".to_string()));
-        assert_eq!(list[0].timestamp.hour(), 9);
+        store.add_weight(make_weight("Emma", 3.62, 16, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.55, 15, 18)).unwrap();
+        store.add_weight(make_weight("Emma", 3.50, 15, 8)).unwrap();
+        store.add_weight(make_weight("Liam", 4.10, 15, 12)).unwrap();
+        store.add_weight(make_weight("Emma", 3.70, 18, 9)).unwrap();
+
+        assert_eq!(
+            store.weight_series(Some("Emma"), ts(15, 0, 0), ts(18, 0, 0)),
+            vec![("2026-02-15".to_string(), 3.55), ("2026-02-16".to_string(), 3.62)]
+        );
+        assert!(store.weight_series(Some("Noah"), ts(1, 0, 0), ts(28, 0, 0)).is_empty());
     }
 
+    // --- Hourly histogram ---
+
     #[test]
-    fn update_feeding_nonexistent_returns_false() {
+    fn feeding_hour_histogram_buckets_by_hour() {
         let mut store = Store::new();
-        let f = make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8);
-        assert!(!store.update_feeding(999, f));
+        store.add_feeding(Feeding::new("Emma".to_string(), FeedingType::Bottle, None, None, None, None, None, None, ts(15, 8, 45)).unwrap()).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, None, 15, 0)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 16, 23)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 12)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 17, 8)).unwrap();
+
+        let h = store.feeding_hour_histogram(Some("Emma"), ts(15, 0, 0), ts(17, 0, 0));
+        assert_eq!(h[0], 1);
+        assert_eq!(h[8], 2);
+        assert_eq!(h[12], 0);
+        assert_eq!(h[23], 1);
+        assert_eq!(h.iter().sum::<u64>(), 4);
     }
 
+    // --- Typical day ---
+
     #[test]
-    fn update_feeding_preserves_id_and_name() {
+    fn typical_day_averages_each_hour_over_the_range() {
         let mut store = Store::new();
-        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        let updated = make_feeding("Someone", FeedingType::Solid, None, None, 15, 10);
-        store.update_feeding(id, updated);
-        let list = store.list_feedings(None, 100);
-        assert_eq!(list[0].id, id);
-        assert_eq!(list[0].baby_name, "Emma");
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(140.0), None, 16, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 16, 20)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(500.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(500.0), None, 17, 8)).unwrap();
+
+        let day = store.typical_day(Some("Emma"), ts(15, 0, 0), ts(17, 0, 0));
+        assert_eq!(day.len(), 24);
+        assert_eq!(day[8], (8, 120.0));
+        assert_eq!(day[20], (20, 45.0));
+        assert_eq!(day[12], (12, 0.0));
+        assert!(store.typical_day(None, ts(15, 0, 0), ts(15, 12, 0)).iter().all(|&(_, ml)| ml == 0.0));
     }
 
-    // --- Dejection CRUD ---
+    // --- Nursing duration histogram ---
 
     #[test]
-    fn add_dejection_assigns_id() {
+    fn nursing_duration_buckets_by_range() {
         let mut store = Store::new();
-        let id = store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8));
-        assert_eq!(id, 1);
+        for (minutes, hour) in [(3, 1), (5, 2), (9, 3), (12, 4), (19, 5), (45, 6)] {
+            store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(minutes), 15, hour)).unwrap();
+        }
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, Some(20), 15, 7)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), Some(15), 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::BreastLeft, None, Some(2), 15, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(2), 16, 10)).unwrap();
+
+        let buckets = store.nursing_duration_buckets(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0));
+        let expected = [("0-5", 1), ("5-10", 2), ("10-20", 2), ("20+", 2)];
+        assert_eq!(buckets, expected.map(|(label, n)| (label.to_string(), n)));
+
+        let empty = store.nursing_duration_buckets(Some("Liam"), ts(15, 0, 0), ts(16, 0, 0));
+        assert!(empty.iter().all(|(_, n)| *n == 0));
+        assert_eq!(empty.len(), 4);
     }
 
+    // --- Next feeding prediction ---
+
     #[test]
-    fn feeding_and_dejection_share_id_counter() {
+    fn predict_next_feeding_uses_median_interval() {
         let mut store = Store::new();
-        let id1 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        let id2 = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9));
-        let id3 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 10));
-        assert_eq!(id1, 1);
-        assert_eq!(id2, 2);
-        assert_eq!(id3, 3);
+        // Gaps: 8h overnight, then 3h, 3h, 2h -> median 3h
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 14, 22)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 6)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 12)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 14)).unwrap();
+
+        assert_eq!(store.predict_next_feeding(None, ts(15, 15, 0)), Some(ts(15, 17, 0)));
     }
 
     #[test]
-    fn delete_dejection() {
+    fn predict_next_feeding_even_count_averages_middle() {
         let mut store = Store::new();
-        let id = store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8));
-        assert!(store.delete_dejection(id));
-        assert!(!store.delete_dejection(id));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 6)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 11)).unwrap();
+
+        assert_eq!(store.predict_next_feeding(None, ts(15, 12, 0)), Some(ts(15, 13, 30)));
     }
 
     #[test]
-    fn update_dejection() {
+    fn predict_next_feeding_needs_two_feedings() {
         let mut store = Store::new();
-        let id = store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8));
-        let updated = Dejection::new("Emma".to_string(), DejectionType::Poop, Some("Note".to_string()), ts(15, 9, 0)).unwrap();
-        assert!(store.update_dejection(id, updated));
-        let timeline = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(timeline[0].subtype, "poop");
-        assert_eq!(timeline[0].notes, Some("Note".to_string()));
+        assert_eq!(store.predict_next_feeding(None, ts(15, 12, 0)), None);
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        assert_eq!(store.predict_next_feeding(None, ts(15, 12, 0)), None);
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 9)).unwrap();
+        assert_eq!(store.predict_next_feeding(Some("Emma"), ts(15, 12, 0)), None);
     }
 
     #[test]
-    fn update_dejection_nonexistent() {
+    fn predict_next_feeding_ignores_future_and_old_feedings() {
         let mut store = Store::new();
-        let d = make_dejection("Emma", DejectionType::Urine, 15, 8);
-        assert!(!store.update_dejection(999, d));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 1, 0)).unwrap();
+        for h in [2, 4, 6, 8, 10, 12, 14, 16] {
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, h)).unwrap();
+        }
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 23)).unwrap();
+
+        assert_eq!(store.predict_next_feeding(None, ts(15, 17, 0)), Some(ts(15, 18, 0)));
     }
 
-    // --- Weight CRUD ---
+    // --- Typical bottle size ---
 
     #[test]
-    fn add_weight_assigns_id() {
+    fn typical_bottle_ml_is_median() {
         let mut store = Store::new();
-        let id = store.add_weight(make_weight("Emma", 3.5, 15, 8));
-        assert_eq!(id, 1);
+        for (h, ml) in [(6, 90.0), (9, 150.0), (12, 120.0), (15, 100.0), (18, 130.0)] {
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(ml), None, 15, h)).unwrap();
+        }
+        // Ignored: no amount, not a bottle
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 20)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, Some(500.0), None, 15, 21)).unwrap();
+
+        assert_eq!(store.typical_bottle_ml(None), Some(120.0));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(140.0), None, 15, 22)).unwrap();
+        assert_eq!(store.typical_bottle_ml(None), Some(125.0));
     }
 
     #[test]
-    fn weight_shares_id_counter() {
+    fn typical_bottle_ml_uses_last_ten() {
         let mut store = Store::new();
-        let id1 = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        let id2 = store.add_weight(make_weight("Emma", 3.5, 15, 9));
-        let id3 = store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 10));
-        assert_eq!(id1, 1);
-        assert_eq!(id2, 2);
-        assert_eq!(id3, 3);
+        for h in 0..5 {
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(30.0), None, 14, h)).unwrap();
+        }
+        for h in 0..10 {
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, h)).unwrap();
+        }
+        assert_eq!(store.typical_bottle_ml(None), Some(100.0));
     }
 
     #[test]
-    fn delete_weight() {
+    fn typical_bottle_ml_needs_three_samples() {
         let mut store = Store::new();
-        let id = store.add_weight(make_weight("Emma", 3.5, 15, 8));
-        assert!(store.delete_weight(id));
-        assert!(!store.delete_weight(id));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 11)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(120.0), None, 15, 12)).unwrap();
+        assert_eq!(store.typical_bottle_ml(Some("Emma")), None);
+        assert_eq!(store.typical_bottle_ml(None), Some(120.0));
     }
 
+    // --- Milk sources ---
+
     #[test]
-    fn update_weight() {
+    fn sources_used_is_distinct_and_sorted() {
         let mut store = Store::new();
-        let id = store.add_weight(make_weight("Emma", 3.5, 15, 8));
-        let updated = Weight::new("Emma".to_string(), 4.0, Some("Gaining".to_string()), ts(15, 10, 0)).unwrap();
-        assert!(store.update_weight(id, updated));
-        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(tl[0].weight_kg, Some(4.0));
-        assert_eq!(tl[0].notes, Some("Gaining".to_string()));
+        for (source, h) in [(Some("pumped"), 8), (Some("donor"), 11), (None, 14), (Some("donor"), 17)] {
+            let mut f = make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, h);
+            f.source = source.map(str::to_string);
+            store.add_feeding(f).unwrap();
+        }
+        let mut noah = make_feeding("Noah", FeedingType::Bottle, Some(90.0), None, 15, 9);
+        noah.source = Some("formula".to_string());
+        store.add_feeding(noah).unwrap();
+
+        assert_eq!(store.sources_used(Some("Emma")), vec!["donor", "pumped"]);
+        assert_eq!(store.sources_used(None), vec!["donor", "formula", "pumped"]);
+        assert!(store.sources_used(Some("Liam")).is_empty());
     }
 
     #[test]
-    fn update_weight_preserves_name() {
+    fn update_feeding_replaces_source() {
         let mut store = Store::new();
-        let id = store.add_weight(make_weight("Emma", 3.5, 15, 8));
-        let updated = Weight::new("Someone".to_string(), 4.0, None, ts(15, 10, 0)).unwrap();
-        store.update_weight(id, updated);
-        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(tl[0].baby_name, "Emma");
+        let mut f = make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 8);
+        f.source = Some("donor".to_string());
+        let id = store.add_feeding(f).unwrap();
+        let updated = make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8);
+        assert!(store.update_feeding(id, updated));
+        assert_eq!(store.feedings[0].source, None);
     }
 
+    // --- Cluster feeding ---
+
     #[test]
-    fn update_weight_nonexistent() {
+    fn cluster_windows_finds_runs() {
         let mut store = Store::new();
-        let w = make_weight("Emma", 3.5, 15, 8);
-        assert!(!store.update_weight(999, w));
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, None, 15, 17)).unwrap();
+        store.add_feeding(Feeding::new("Emma".to_string(), FeedingType::BreastRight, None, None, None, None, None, None, ts(15, 17, 40)).unwrap()).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, None, 15, 18)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, None, 15, 22)).unwrap();
+
+        let clusters = store.cluster_windows(None, 3, 45);
+        assert_eq!(clusters, vec![(ts(15, 17, 0), ts(15, 18, 0), 3)]);
     }
 
-    // --- Unified timeline ---
+    #[test]
+    fn cluster_windows_threshold_is_inclusive() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 9)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 10)).unwrap();
+
+        assert_eq!(store.cluster_windows(None, 3, 60), vec![(ts(15, 8, 0), ts(15, 10, 0), 3)]);
+        assert!(store.cluster_windows(None, 3, 59).is_empty());
+    }
 
     #[test]
-    fn timeline_merges_feedings_and_dejections() {
+    fn cluster_windows_filters_by_name() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9));
-        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, None, 15, 10));
-        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 11));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 9)).unwrap();
 
-        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(tl.len(), 4);
-        assert_eq!(tl[0].kind, "feeding");
-        assert_eq!(tl[0].timestamp.hour(), 8);
-        assert_eq!(tl[1].kind, "dejection");
-        assert_eq!(tl[1].subtype, "urine");
-        assert_eq!(tl[2].kind, "feeding");
-        assert_eq!(tl[3].kind, "dejection");
-        assert_eq!(tl[3].subtype, "poop");
+        assert!(store.cluster_windows(Some("Emma"), 3, 60).is_empty());
+        assert_eq!(store.cluster_windows(None, 3, 60).len(), 1);
     }
 
+    // --- Nursing sessions ---
+
     #[test]
-    fn timeline_includes_weights() {
+    fn merged_nursing_sessions_join_left_and_right() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        store.add_weight(make_weight("Emma", 4.2, 15, 10));
+        let feed = |ft, minutes, h, m| Feeding::new("Emma".to_string(), ft, None, None, minutes, None, None, None, ts(15, h, m)).unwrap();
+        store.add_feeding(feed(FeedingType::BreastLeft, Some(12), 8, 0)).unwrap();
+        store.add_feeding(feed(FeedingType::BreastRight, Some(10), 8, 15)).unwrap();
+        store.add_feeding(feed(FeedingType::BreastLeft, Some(15), 11, 0)).unwrap();
+        store.add_feeding(feed(FeedingType::Bottle, None, 11, 20)).unwrap();
+        store.add_feeding(feed(FeedingType::BreastRight, Some(5), 11, 25)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::BreastLeft, None, Some(20), 15, 8)).unwrap();
 
-        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(tl.len(), 2);
-        assert_eq!(tl[1].kind, "weight");
-        assert_eq!(tl[1].weight_kg, Some(4.2));
+        let sessions = store.merged_nursing_sessions(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0), 5);
+        assert_eq!(sessions, vec![(ts(15, 8, 0), 22), (ts(15, 11, 0), 15), (ts(15, 11, 25), 5)]);
+
+        let tight = store.merged_nursing_sessions(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0), 2);
+        assert_eq!(tight[..2], [(ts(15, 8, 0), 12), (ts(15, 8, 15), 10)]);
+        assert_eq!(store.merged_nursing_sessions(None, ts(15, 0, 0), ts(16, 0, 0), 5).len(), 4);
     }
 
+    // --- Diaper gaps ---
+
     #[test]
-    fn timeline_chronological_order() {
+    fn diaper_gaps_in_minutes() {
         let mut store = Store::new();
-        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 14));
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 6));
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 11)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8)).unwrap();
+        store.add_dejection(Dejection::new("Emma".to_string(), DejectionType::Urine, false, None, ts(15, 9, 30)).unwrap()).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 16, 8)).unwrap();
 
-        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert!(tl[0].timestamp < tl[1].timestamp);
-        assert!(tl[1].timestamp < tl[2].timestamp);
+        assert_eq!(store.diaper_gaps(None, ts(15, 0, 0), ts(16, 0, 0)), vec![90, 90]);
     }
 
     #[test]
-    fn timeline_filters_by_day() {
+    fn diaper_gaps_needs_two() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 14, 20));
-        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8));
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 16, 6));
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Noah", DejectionType::Urine, 15, 9)).unwrap();
+        assert!(store.diaper_gaps(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0)).is_empty());
+    }
 
-        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(tl.len(), 1);
-        assert_eq!(tl[0].kind, "dejection");
+    // --- Calorie estimate ---
+
+    #[test]
+    fn estimated_calories_default_factors() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, Some(50.0), Some(10), 15, 10)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastRight, None, Some(15), 15, 12)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, Some(30.0), None, 15, 13)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 16, 8)).unwrap();
+
+        let kcal = store.estimated_calories(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert!((kcal - (150.0 * 0.67 + 50.0)).abs() < 1e-9);
     }
 
     #[test]
-    fn timeline_filters_by_name() {
+    fn estimated_calories_custom_factors_persist() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        store.add_dejection(make_dejection("Noah", DejectionType::Poop, 15, 9));
+        store.set_calorie_factors(0.7, 80.0);
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Solid, None, None, 15, 12)).unwrap();
 
-        let tl = store.timeline_for_day(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(tl.len(), 1);
-        assert_eq!(tl[0].baby_name, "Emma");
+        let restored = Store::from_json(&store.to_json()).unwrap();
+        let kcal = restored.estimated_calories(None, ts(15, 0, 0), ts(16, 0, 0));
+        assert!((kcal - 150.0).abs() < 1e-9);
     }
 
     #[test]
-    fn timeline_empty() {
-        let store = Store::new();
-        let tl = store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert!(tl.is_empty());
+    fn json_without_calorie_factors_uses_defaults() {
+        let json = r#"{"feedings":[{"id":1,"baby_name":"Emma","feeding_type":"solid","amount_ml":null,"duration_minutes":null,"notes":null,"timestamp":"2026-02-15T08:00:00"}],"next_id":2}"#;
+        let store = Store::from_json(json).unwrap();
+        assert_eq!(store.estimated_calories(None, ts(15, 0, 0), ts(16, 0, 0)), DEFAULT_KCAL_PER_SOLID);
     }
 
-    // --- JSON persistence ---
+    // --- Daily goal ---
 
     #[test]
-    fn json_roundtrip_preserves_data() {
+    fn goal_progress_sums_the_day() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 8));
-        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9));
-        store.add_weight(make_weight("Emma", 3.5, 15, 10));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(150.0), None, 15, 12)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 15, 14)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 16, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(90.0), None, 15, 9)).unwrap();
+        let day = NaiveDate::from_ymd_opt(2026, 2, 15).unwrap();
 
-        let json = store.to_json();
-        let restored = Store::from_json(&json).unwrap();
-        let tl = restored.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(tl.len(), 3);
-        assert_eq!(tl[0].kind, "feeding");
-        assert_eq!(tl[1].kind, "dejection");
-        assert_eq!(tl[2].kind, "weight");
+        assert_eq!(store.goal_progress(Some("Emma"), day), None);
+        store.set_daily_goal_ml(600.0);
+        assert_eq!(store.goal_progress(Some("Emma"), day), Some((270.0, 600.0)));
+
+        let restored = Store::from_json(&store.to_json()).unwrap();
+        assert_eq!(restored.goal_progress(None, day), Some((360.0, 600.0)));
+
+        store.set_daily_goal_ml(0.0);
+        assert_eq!(store.goal_progress(Some("Emma"), day), None);
     }
 
+    // --- Intake per kg ---
+
     #[test]
-    fn json_roundtrip_preserves_next_id() {
+    fn ml_per_kg_per_day_uses_latest_weight_so_far() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8));
-        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9));
+        let day = NaiveDate::from_ymd_opt(2026, 2, 15).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(200.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(160.0), None, 15, 14)).unwrap();
+        assert_eq!(store.ml_per_kg_per_day(Some("Emma"), day), None);
 
-        let json = store.to_json();
-        let mut restored = Store::from_json(&json).unwrap();
-        let id3 = restored.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 10));
-        assert_eq!(id3, 3);
+        store.add_weight(make_weight("Emma", 3.4, 10, 9)).unwrap();
+        store.add_weight(make_weight("Emma", 3.6, 15, 20)).unwrap();
+        store.add_weight(make_weight("Emma", 3.9, 16, 9)).unwrap();
+        assert_eq!(store.ml_per_kg_per_day(Some("Emma"), day), Some(100.0));
+        assert_eq!(store.ml_per_kg_per_day(Some("Noah"), day), None);
     }
 
+    // --- Volume rounding ---
+
     #[test]
-    fn json_backwards_compat_no_dejections_field() {
-        let json = r#"{"feedings":[],"next_id":1}"#;
-        let store = Store::from_json(json).unwrap();
-        assert!(store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0)).is_empty());
+    fn volume_rounding_ties_to_even() {
+        let mut store = Store::new();
+        store.set_volume_rounding(5.0);
+        let a = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(62.5), None, 15, 8)).unwrap();
+        let b = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(67.5), None, 15, 9)).unwrap();
+        let c = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(63.0), None, 15, 10)).unwrap();
+        let d = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(61.9), None, 15, 11)).unwrap();
+        assert_eq!(store.get_feeding(a).unwrap().amount_ml, Some(60.0));
+        assert_eq!(store.get_feeding(b).unwrap().amount_ml, Some(70.0));
+        assert_eq!(store.get_feeding(c).unwrap().amount_ml, Some(65.0));
+        assert_eq!(store.get_feeding(d).unwrap().amount_ml, Some(60.0));
     }
 
     #[test]
-    fn json_backwards_compat_no_weights_field() {
-        let json = r#"{"feedings":[],"dejections":[],"next_id":1}"#;
-        let store = Store::from_json(json).unwrap();
-        assert!(store.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0)).is_empty());
+    fn volume_rounding_applies_on_update_and_patch() {
+        let mut store = Store::new();
+        let id = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(62.5), None, 15, 8)).unwrap();
+        store.set_volume_rounding(10.0);
+        store.update_feeding(id, make_feeding("Emma", FeedingType::Bottle, Some(87.0), None, 15, 8));
+        assert_eq!(store.get_feeding(id).unwrap().amount_ml, Some(90.0));
+        store.patch_feeding(id, FeedingPatch { amount_ml: Some(44.0), ..Default::default() }).unwrap();
+        assert_eq!(store.get_feeding(id).unwrap().amount_ml, Some(40.0));
     }
 
     #[test]
-    fn from_json_invalid_returns_error() {
-        assert!(Store::from_json("not json").is_err());
+    fn volume_rounding_disabled() {
+        let mut store = Store::new();
+        let a = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(62.5), None, 15, 8)).unwrap();
+        store.set_volume_rounding(5.0);
+        store.set_volume_rounding(0.0);
+        let b = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(62.5), None, 15, 9)).unwrap();
+        store.set_volume_rounding(-5.0);
+        let c = store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(62.5), None, 15, 10)).unwrap();
+        for id in [a, b, c] {
+            assert_eq!(store.get_feeding(id).unwrap().amount_ml, Some(62.5));
+        }
     }
 
-    // --- Summary (bounded) ---
+    // --- Name matching ---
 
     #[test]
-    fn summary_includes_dejection_counts() {
+    fn name_matching_exact_by_default() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8));
-        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 9));
-        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 11));
-        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 13));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("emma", FeedingType::Bottle, Some(50.0), None, 15, 9)).unwrap();
+        assert_eq!(store.list_feedings(Some("emma"), 100).len(), 1);
+        assert_eq!(store.summary_per_baby(ts(15, 0, 0), ts(16, 0, 0)).len(), 2);
+    }
 
-        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(s.total_feedings, 1);
-        assert_eq!(s.total_urine, 2);
-        assert_eq!(s.total_poop, 1);
+    #[test]
+    fn name_matching_case_insensitive() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("emma", FeedingType::Bottle, Some(50.0), None, 15, 9)).unwrap();
+        store.add_dejection(make_dejection("EMMA", DejectionType::Urine, 15, 10)).unwrap();
+        store.set_name_matching(NameMatching::CaseInsensitive);
+
+        assert_eq!(store.list_feedings(Some("emma"), 100).len(), 2);
+        assert_eq!(store.timeline_for_day(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0)).len(), 3);
+        assert_eq!(store.summary(Some("eMMa"), ts(15, 0, 0), ts(16, 0, 0)).total_ml, 150.0);
+
+        let per_baby = store.summary_per_baby(ts(15, 0, 0), ts(16, 0, 0));
+        assert_eq!(per_baby.len(), 1);
+        assert_eq!(per_baby[0].1.total_feedings, 2);
+
+        let restored = Store::from_json(&store.to_json()).unwrap();
+        assert_eq!(restored.list_feedings(Some("emma"), 100).len(), 2);
     }
 
+    // --- Locale ---
+
     #[test]
-    fn summary_bounded_excludes_other_days() {
+    fn locale_defaults_to_english_and_persists() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(100.0), None, 14, 8));
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8));
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 16, 8));
+        assert_eq!(store.locale(), Locale::En);
+        store.set_locale(Locale::Es);
+        assert_eq!(Store::from_json(&store.to_json()).unwrap().locale(), Locale::Es);
+        assert_eq!(Store::from_ndjson(&store.to_ndjson()).unwrap().locale(), Locale::Es);
+    }
 
-        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(s.total_feedings, 1);
-        assert_eq!(s.total_ml, 120.0);
+    // --- Unit system ---
+
+    #[test]
+    fn unit_system_defaults_to_metric_and_persists() {
+        let mut store = Store::new();
+        assert_eq!(store.unit_system(), UnitSystem::Metric);
+        store.set_unit_system(UnitSystem::Imperial);
+        assert_eq!(Store::from_json(&store.to_json()).unwrap().unit_system(), UnitSystem::Imperial);
     }
 
     #[test]
-    fn summary_includes_latest_weight() {
+    fn lenient_feeding_types_default_off_and_persist() {
         let mut store = Store::new();
-        store.add_weight(make_weight("Emma", 3.5, 15, 8));
-        store.add_weight(make_weight("Emma", 3.6, 15, 14));
+        assert!(!store.lenient_feeding_types());
+        store.set_lenient_feeding_types(true);
+        assert!(Store::from_json(&store.to_json()).unwrap().lenient_feeding_types());
+    }
 
-        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(s.latest_weight_kg, Some(3.6));
+    #[test]
+    fn strict_timestamps_default_off_and_persist() {
+        let mut store = Store::new();
+        assert!(!store.strict_timestamps());
+        store.set_strict_timestamps(true);
+        assert!(Store::from_json(&store.to_json()).unwrap().strict_timestamps());
+        assert!(Store::from_bincode(&store.to_bincode()).unwrap().strict_timestamps());
     }
 
+    // --- Dejection labels ---
+
     #[test]
-    fn summary_no_weight() {
-        let store = Store::new();
-        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(s.latest_weight_kg, None);
+    fn dejection_labels_reach_timeline_and_persist() {
+        let mut store = Store::new();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 9)).unwrap();
+        let labels = |s: &Store| -> Vec<Option<String>> {
+            s.timeline_for_day(None, ts(15, 0, 0), ts(16, 0, 0)).into_iter().map(|e| e.label).collect()
+        };
+        assert_eq!(labels(&store), vec![None, None]);
+
+        store.set_dejection_labels(" 💧 ".to_string(), "💩".to_string());
+        assert_eq!(labels(&store), vec![Some("💧".to_string()), Some("💩".to_string())]);
+        let restored = Store::from_json(&store.to_json()).unwrap();
+        assert_eq!(restored.dejection_label(&DejectionType::Poop), Some("💩"));
     }
 
+    // --- Display format ---
+
     #[test]
-    fn summary_empty_store() {
-        let store = Store::new();
-        let s = store.summary(None, ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(s.total_feedings, 0);
-        assert_eq!(s.total_ml, 0.0);
-        assert_eq!(s.total_minutes, 0);
-        assert_eq!(s.total_urine, 0);
-        assert_eq!(s.total_poop, 0);
-        assert_eq!(s.latest_weight_kg, None);
+    fn display_format_applies_and_persists() {
+        let mut store = Store::new();
+        assert_eq!(store.format_timestamp(ts(15, 8, 5)), "2026-02-15 08:05");
+        store.set_display_format("%d/%m/%Y %H:%M".to_string()).unwrap();
+        assert_eq!(store.format_timestamp(ts(15, 8, 5)), "15/02/2026 08:05");
+        let restored = Store::from_json(&store.to_json()).unwrap();
+        assert_eq!(restored.display_format(), "%d/%m/%Y %H:%M");
     }
 
     #[test]
-    fn summary_filters_by_name() {
+    fn display_format_rejects_bad_specifiers() {
         let mut store = Store::new();
-        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 8));
-        store.add_dejection(make_dejection("Noah", DejectionType::Poop, 15, 9));
+        for bad in ["%Q", "%Y-%", "%z", "  "] {
+            assert_eq!(store.set_display_format(bad.to_string()), Err(TrackerError::InvalidDisplayFormat(bad.to_string())));
+        }
+        assert_eq!(store.display_format(), "%Y-%m-%d %H:%M");
+    }
 
-        let s = store.summary(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0));
-        assert_eq!(s.total_poop, 1);
+    #[test]
+    fn validate_uses_display_format() {
+        let mut store = Store::new();
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, None, None, 15, 8)).unwrap();
+        store.set_display_format("%d/%m %H:%M".to_string()).unwrap();
+        assert_eq!(store.validate(ts(16, 0, 0)), vec!["Feeding #1 (15/02 08:00) is a bottle with no amount"]);
     }
 
     // --- Report ---
 
+    #[test]
+    fn report_by_daypart_splits_each_day() {
+        let mut store = Store::new();
+        for (hour, ml) in [(2, 90.0), (6, 100.0), (11, 110.0), (12, 120.0), (17, 60.0), (18, 130.0), (22, 140.0), (23, 50.0)] {
+            store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(ml), None, 15, hour)).unwrap();
+        }
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 16, 3)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(70.0), None, 15, 9)).unwrap();
+
+        let rows = store.report_by_daypart(Some("Emma"), ts(15, 0, 0), ts(17, 0, 0));
+        assert_eq!(rows.len(), 2);
+        let bucket = |feedings, total_ml| DaypartBucket { feedings, total_ml };
+        assert_eq!(rows[0].date, "2026-02-15");
+        assert_eq!(rows[0].morning, bucket(2, 210.0));
+        assert_eq!(rows[0].afternoon, bucket(2, 180.0));
+        assert_eq!(rows[0].evening, bucket(1, 130.0));
+        assert_eq!(rows[0].night, bucket(3, 280.0));
+        assert_eq!(rows[1].night, bucket(1, 0.0));
+        assert_eq!(rows[1].morning, bucket(0, 0.0));
+    }
+
     #[test]
     fn report_aggregates_per_day() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 14, 8));
-        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 14, 12));
-        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 14, 10));
-        store.add_weight(make_weight("Emma", 3.5, 14, 9));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 14, 8)).unwrap();
+        store.add_feeding(make_feeding("Emma", FeedingType::BreastLeft, None, Some(15), 14, 12)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Urine, 14, 10)).unwrap();
+        store.add_weight(make_weight("Emma", 3.5, 14, 9)).unwrap();
 
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 8));
-        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 10));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(90.0), None, 15, 8)).unwrap();
+        store.add_dejection(make_dejection("Emma", DejectionType::Poop, 15, 10)).unwrap();
 
         let r = store.report(None, ts(14, 0, 0), ts(16, 0, 0));
         assert_eq!(r.len(), 2);
@@ -792,11 +4753,23 @@ mod tests {
     #[test]
     fn report_filters_by_name() {
         let mut store = Store::new();
-        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8));
-        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(100.0), None, 15, 9));
+        store.add_feeding(make_feeding("Emma", FeedingType::Bottle, Some(120.0), None, 15, 8)).unwrap();
+        store.add_feeding(make_feeding("Noah", FeedingType::Bottle, Some(100.0), None, 15, 9)).unwrap();
 
         let r = store.report(Some("Emma"), ts(15, 0, 0), ts(16, 0, 0));
         assert_eq!(r[0].total_feedings, 1);
         assert_eq!(r[0].total_ml, 120.0);
     }
+
+    #[test]
+    fn report_carries_iso_week() {
+        let store = Store::new();
+        let r = store.report(None, ts(14, 0, 0), ts(17, 0, 0));
+        let weeks: Vec<(i32, u32)> = r.iter().map(|d| (d.iso_year, d.iso_week)).collect();
+        assert_eq!(weeks, vec![(2026, 7), (2026, 7), (2026, 8)]);
+
+        let new_year = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        let r = store.report(None, new_year, new_year + chrono::Duration::days(1));
+        assert_eq!((r[0].iso_year, r[0].iso_week), (2026, 53));
+    }
 }
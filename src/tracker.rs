@@ -1,12 +1,27 @@
-use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
 
-use crate::models::{Dejection, DejectionType, Feeding, FeedingType, Weight};
+use chrono::{DateTime, NaiveDateTime, Timelike};
+use serde::Deserialize;
+
+use crate::error::TrackerError;
+use crate::models::{
+    BabyMeta, Dejection, DejectionType, Feeding, FeedingPatch, FeedingType, Locale, NameMatching, TimelineEntry, UnitSystem, Weight,
+};
 use crate::store::Store;
 
+/// Feedings matching an existing one within this many seconds count as duplicates.
+const DEDUPE_WINDOW_SECONDS: i64 = 60;
+
 pub struct Tracker {
     store: Store,
 }
 
+impl Default for Tracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Tracker {
     pub fn new() -> Self {
         Tracker {
@@ -14,49 +29,262 @@ impl Tracker {
         }
     }
 
-    pub fn from_json(json: &str) -> Result<Self, String> {
+    pub fn from_json(json: &str) -> Result<Self, TrackerError> {
         Ok(Tracker {
             store: Store::from_json(json)?,
         })
     }
 
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, TrackerError> {
+        Ok(Tracker {
+            store: Store::from_bincode(bytes)?,
+        })
+    }
+
+    pub fn from_ndjson(s: &str) -> Result<Self, TrackerError> {
+        Ok(Tracker {
+            store: Store::from_ndjson(s)?,
+        })
+    }
+
+    pub fn json_schema() -> String {
+        Store::json_schema()
+    }
+
     pub fn export_data(&self) -> String {
         self.store.to_json()
     }
 
+    pub fn entry_count(&self) -> usize {
+        self.store.entry_count()
+    }
+
+    /// Set when the loaded export's integrity `meta` did not match its records.
+    pub fn load_warning(&self) -> Option<String> {
+        self.store.load_warning().map(str::to_string)
+    }
+
+    /// True when something changed since the last full export.
+    pub fn is_dirty(&self) -> bool {
+        self.store.is_dirty()
+    }
+
+    pub fn export_baby_data(&self, baby_name: &str) -> String {
+        self.store.export_baby_json(baby_name)
+    }
+
+    pub fn export_bincode(&self) -> Vec<u8> {
+        self.store.to_bincode()
+    }
+
+    pub fn export_ndjson(&self) -> String {
+        self.store.to_ndjson()
+    }
+
+    /// Merges another exported JSON store into this one; see `Store::merge`.
+    pub fn merge_from(&mut self, json: &str) -> Result<usize, TrackerError> {
+        let other = Store::from_json(json)?;
+        self.store.merge(other)
+    }
+
+    /// JSON `{only_in_self, only_in_other}` against another exported store; see
+    /// `Store::diff`.
+    pub fn diff_with(&self, json: &str) -> Result<String, TrackerError> {
+        let other = Store::from_json(json)?;
+        let diff = self.store.diff(&other);
+        Ok(serde_json::to_string(&diff).unwrap_or_else(|_| "{}".to_string()))
+    }
+
+    pub fn delete_range(&mut self, baby_name: Option<&str>, start: &str, end: &str) -> Result<usize, TrackerError> {
+        let start = self.read_timestamp(start)?;
+        let end = self.read_timestamp(end)?;
+        Ok(self.store.delete_range(baby_name, start, end))
+    }
+
+    pub fn prune_older_than(&mut self, cutoff: &str, keep_weights: bool) -> Result<usize, TrackerError> {
+        let cutoff = self.read_timestamp(cutoff)?;
+        Ok(self.store.prune_older_than(cutoff, keep_weights))
+    }
+
+    pub fn delete_by_kind(&mut self, kind: &str, baby_name: Option<&str>) -> usize {
+        self.store.delete_by_kind(kind, baby_name)
+    }
+
+    pub fn clear(&mut self) {
+        self.store.clear()
+    }
+
+    pub fn reindex(&mut self) {
+        self.store.reindex()
+    }
+
+    /// Rounds future feeding amounts to the nearest `step` ml; 0 disables.
+    pub fn set_volume_rounding(&mut self, step: f64) {
+        self.store.set_volume_rounding(step)
+    }
+
+    /// `"exact"` (the default) or `"case-insensitive"`, applied to every
+    /// `baby_name` filter.
+    pub fn set_name_matching(&mut self, mode: &str) -> Result<(), TrackerError> {
+        self.store.set_name_matching(NameMatching::parse(mode)?);
+        Ok(())
+    }
+
+    /// A timestamp argument, in the full form only when strict timestamps are on.
+    fn read_timestamp(&self, s: &str) -> Result<NaiveDateTime, TrackerError> {
+        if self.store.strict_timestamps() {
+            parse_timestamp_strict(s)
+        } else {
+            parse_timestamp(s)
+        }
+    }
+
+    /// Timestamp of an `add_entry` payload; relative forms are only resolved
+    /// against `now` when strict timestamps are off.
+    fn entry_timestamp(&self, timestamp: &str, now: Option<&str>) -> Result<NaiveDateTime, TrackerError> {
+        if self.store.strict_timestamps() {
+            parse_timestamp_strict(timestamp)
+        } else {
+            resolve_timestamp(timestamp, now)
+        }
+    }
+
     // --- Feeding ---
 
+    /// Use `add_entry` for relative timestamps (`"now": ...`) or to reject a
+    /// double-tapped duplicate (`"dedupe": true`).
+    #[allow(clippy::too_many_arguments)]
     pub fn add_feeding(
         &mut self,
         baby_name: &str,
         feeding_type: &str,
         amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
+        duration_minutes: Option<u32>,
+        notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
+        timestamp: &str,
+    ) -> Result<u64, TrackerError> {
+        let ft = self.parse_feeding_type(feeding_type)?;
+        let ts = self.read_timestamp(timestamp)?;
+        let feeding = Feeding::new(baby_name.to_string(), ft, amount_ml, leftover_ml, duration_minutes, notes, source, fussiness, ts)?;
+        self.store.add_feeding(feeding)
+    }
+
+    /// One feeding per name with identical data (tandem feeding twins), ids in
+    /// `names` order. Every name is validated and enough ids reserved before
+    /// anything is stored, so a failure adds nothing; an empty list is `EmptyName`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_feeding_multi(
+        &mut self,
+        names: Vec<String>,
+        feeding_type: &str,
+        amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
         duration_minutes: Option<u32>,
         notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
         timestamp: &str,
-    ) -> Result<u32, String> {
-        let ft = FeedingType::parse(feeding_type)?;
-        let ts = parse_timestamp(timestamp)?;
-        let feeding = Feeding::new(baby_name.to_string(), ft, amount_ml, duration_minutes, notes, ts)?;
-        Ok(self.store.add_feeding(feeding))
+    ) -> Result<Vec<u64>, TrackerError> {
+        if names.is_empty() {
+            return Err(TrackerError::EmptyName);
+        }
+        let ft = self.parse_feeding_type(feeding_type)?;
+        let ts = self.read_timestamp(timestamp)?;
+        let feedings = names
+            .into_iter()
+            .map(|name| {
+                Feeding::new(name, ft.clone(), amount_ml, leftover_ml, duration_minutes, notes.clone(), source.clone(), fussiness, ts)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.store.ensure_ids_available(feedings.len())?;
+        feedings.into_iter().map(|f| self.store.add_feeding(f)).collect()
     }
 
+    /// One-tap logging: a feeding at `now` with no amount, duration or notes.
+    pub fn add_quick_feeding(&mut self, baby_name: &str, feeding_type: &str, now: &str) -> Result<u64, TrackerError> {
+        self.add_feeding(baby_name, feeding_type, None, None, None, None, None, None, now)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn update_feeding(
         &mut self,
-        id: u32,
+        id: u64,
+        feeding_type: &str,
+        amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
+        duration_minutes: Option<u32>,
+        notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
+        timestamp: &str,
+    ) -> Result<bool, TrackerError> {
+        let changed = self.update_feeding_reporting(
+            id,
+            feeding_type,
+            amount_ml,
+            leftover_ml,
+            duration_minutes,
+            notes,
+            source,
+            fussiness,
+            timestamp,
+        )?;
+        Ok(changed.is_some())
+    }
+
+    /// Same arguments as `update_feeding`. `None` when no feeding has this id,
+    /// otherwise whether any stored value actually changed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_feeding_reporting(
+        &mut self,
+        id: u64,
         feeding_type: &str,
         amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
         duration_minutes: Option<u32>,
         notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
         timestamp: &str,
-    ) -> Result<bool, String> {
-        let ft = FeedingType::parse(feeding_type)?;
-        let ts = parse_timestamp(timestamp)?;
-        let updated = Feeding::new("x".to_string(), ft, amount_ml, duration_minutes, notes, ts)?;
-        Ok(self.store.update_feeding(id, updated))
+    ) -> Result<Option<bool>, TrackerError> {
+        let ft = self.parse_feeding_type(feeding_type)?;
+        let ts = self.read_timestamp(timestamp)?;
+        let updated = Feeding::new("x".to_string(), ft, amount_ml, leftover_ml, duration_minutes, notes, source, fussiness, ts)?;
+        Ok(self.store.update_feeding_reporting(id, updated))
+    }
+
+    /// Like `update_feeding`, but every field is optional and `None` keeps the
+    /// stored value. Returns false when no feeding has this id.
+    #[allow(clippy::too_many_arguments)]
+    pub fn patch_feeding(
+        &mut self,
+        id: u64,
+        feeding_type: Option<&str>,
+        amount_ml: Option<f64>,
+        leftover_ml: Option<f64>,
+        duration_minutes: Option<u32>,
+        notes: Option<String>,
+        source: Option<String>,
+        fussiness: Option<u8>,
+        timestamp: Option<&str>,
+    ) -> Result<bool, TrackerError> {
+        let patch = FeedingPatch {
+            feeding_type: feeding_type.map(|s| self.parse_feeding_type(s)).transpose()?,
+            amount_ml,
+            leftover_ml,
+            duration_minutes,
+            notes,
+            source,
+            fussiness,
+            timestamp: timestamp.map(|s| self.read_timestamp(s)).transpose()?,
+        };
+        self.store.patch_feeding(id, patch)
     }
 
-    pub fn delete_feeding(&mut self, id: u32) -> bool {
+    pub fn delete_feeding(&mut self, id: u64) -> bool {
         self.store.delete_feeding(id)
     }
 
@@ -66,29 +294,31 @@ impl Tracker {
         &mut self,
         baby_name: &str,
         dejection_type: &str,
+        rash: bool,
         notes: Option<String>,
         timestamp: &str,
-    ) -> Result<u32, String> {
+    ) -> Result<u64, TrackerError> {
         let dt = DejectionType::parse(dejection_type)?;
-        let ts = parse_timestamp(timestamp)?;
-        let dejection = Dejection::new(baby_name.to_string(), dt, notes, ts)?;
-        Ok(self.store.add_dejection(dejection))
+        let ts = self.read_timestamp(timestamp)?;
+        let dejection = Dejection::new(baby_name.to_string(), dt, rash, notes, ts)?;
+        self.store.add_dejection(dejection)
     }
 
     pub fn update_dejection(
         &mut self,
-        id: u32,
+        id: u64,
         dejection_type: &str,
+        rash: bool,
         notes: Option<String>,
         timestamp: &str,
-    ) -> Result<bool, String> {
+    ) -> Result<bool, TrackerError> {
         let dt = DejectionType::parse(dejection_type)?;
-        let ts = parse_timestamp(timestamp)?;
-        let updated = Dejection::new("x".to_string(), dt, notes, ts)?;
+        let ts = self.read_timestamp(timestamp)?;
+        let updated = Dejection::new("x".to_string(), dt, rash, notes, ts)?;
         Ok(self.store.update_dejection(id, updated))
     }
 
-    pub fn delete_dejection(&mut self, id: u32) -> bool {
+    pub fn delete_dejection(&mut self, id: u64) -> bool {
         self.store.delete_dejection(id)
     }
 
@@ -100,274 +330,1879 @@ impl Tracker {
         weight_kg: f64,
         notes: Option<String>,
         timestamp: &str,
-    ) -> Result<u32, String> {
-        let ts = parse_timestamp(timestamp)?;
+    ) -> Result<u64, TrackerError> {
+        let ts = self.read_timestamp(timestamp)?;
         let weight = Weight::new(baby_name.to_string(), weight_kg, notes, ts)?;
-        Ok(self.store.add_weight(weight))
+        self.store.add_weight(weight)
     }
 
     pub fn update_weight(
         &mut self,
-        id: u32,
+        id: u64,
         weight_kg: f64,
         notes: Option<String>,
         timestamp: &str,
-    ) -> Result<bool, String> {
-        let ts = parse_timestamp(timestamp)?;
+    ) -> Result<bool, TrackerError> {
+        let ts = self.read_timestamp(timestamp)?;
         let updated = Weight::new("x".to_string(), weight_kg, notes, ts)?;
         Ok(self.store.update_weight(id, updated))
     }
 
-    pub fn delete_weight(&mut self, id: u32) -> bool {
+    pub fn delete_weight(&mut self, id: u64) -> bool {
         self.store.delete_weight(id)
     }
 
-    // --- Timeline ---
+    // --- Generic add ---
 
-    pub fn timeline_for_day(&self, baby_name: Option<&str>, date: &str) -> Result<String, String> {
-        let day_start = parse_timestamp(&format!("{}T00:00:00", date))?;
-        let day_end = day_start + chrono::Duration::days(1);
-        let entries = self.store.timeline_for_day(baby_name, day_start, day_end);
-        Ok(serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string()))
+    /// Single entry point for dynamic UIs: `kind` is `"feeding"`, `"dejection"`
+    /// or `"weight"` and `json` an object whose fields are named after the
+    /// matching `add_*` arguments. Each payload may also carry `now`, making
+    /// `timestamp` relative to it (`now`, `now-30m`, `now-2h`), and a feeding an
+    /// optional `dedupe` flag. Returns JSON `{id, kind}`.
+    pub fn add_entry(&mut self, kind: &str, json: &str) -> Result<String, TrackerError> {
+        fn payload<'a, T: Deserialize<'a>>(json: &'a str) -> Result<T, TrackerError> {
+            serde_json::from_str(json).map_err(|e| TrackerError::InvalidData(e.to_string()))
+        }
+        let (id, kind) = match kind.trim().to_lowercase().as_str() {
+            "feeding" => {
+                let p: FeedingInput = payload(json)?;
+                let ft = self.parse_feeding_type(&p.feeding_type)?;
+                let ts = self.entry_timestamp(&p.timestamp, p.now.as_deref())?;
+                let feeding = Feeding::new(p.baby_name, ft, p.amount_ml, p.leftover_ml, p.duration_minutes, p.notes, p.source, p.fussiness, ts)?;
+                // Opt-in: a feeding matching one already logged for the same baby
+                // (type, amount, and time within a minute) is a double tap.
+                if p.dedupe && self.store.is_duplicate_feeding(&feeding, DEDUPE_WINDOW_SECONDS) {
+                    return Err(TrackerError::DuplicateFeeding);
+                }
+                (self.store.add_feeding(feeding)?, "feeding")
+            }
+            "dejection" => {
+                let p: DejectionInput = payload(json)?;
+                let dt = DejectionType::parse(&p.dejection_type)?;
+                let ts = self.entry_timestamp(&p.timestamp, p.now.as_deref())?;
+                let dejection = Dejection::new(p.baby_name, dt, p.rash, p.notes, ts)?;
+                (self.store.add_dejection(dejection)?, "dejection")
+            }
+            "weight" => {
+                let p: WeightInput = payload(json)?;
+                let ts = self.entry_timestamp(&p.timestamp, p.now.as_deref())?;
+                let weight = Weight::new(p.baby_name, p.weight_kg, p.notes, ts)?;
+                (self.store.add_weight(weight)?, "weight")
+            }
+            _ => return Err(TrackerError::InvalidData(format!("unknown entry kind '{}'", kind))),
+        };
+        Ok(serde_json::json!({ "id": id, "kind": kind }).to_string())
     }
 
-    // --- Summary (day-bounded) ---
+    pub fn latest_of_each(&self, baby_name: Option<&str>) -> String {
+        let (feeding, dejection, weight) = self.store.latest_of_each(baby_name);
+        serde_json::json!({ "feeding": feeding, "dejection": dejection, "weight": weight }).to_string()
+    }
 
-    pub fn get_summary(&self, baby_name: Option<&str>, date: &str) -> Result<String, String> {
-        let since = parse_timestamp(&format!("{}T00:00:00", date))?;
-        let until = since + chrono::Duration::days(1);
-        let summary = self.store.summary(baby_name, since, until);
-        Ok(serde_json::to_string(&summary).unwrap_or_else(|_| "{}".to_string()))
+    /// Timeline entry JSON for the event just before `timestamp`, or `"null"`.
+    pub fn last_entry_before(&self, baby_name: Option<&str>, timestamp: &str) -> Result<String, TrackerError> {
+        let before = self.read_timestamp(timestamp)?;
+        let entry = self.store.last_entry_before(baby_name, before);
+        Ok(serde_json::to_string(&entry).unwrap_or_else(|_| "null".to_string()))
     }
 
-    // --- Report (date range) ---
+    /// Today's counts, last feeding and latest weight in one JSON object.
+    pub fn glance(&self, baby_name: Option<&str>, now: &str) -> Result<String, TrackerError> {
+        let now = self.read_timestamp(now)?;
+        let glance = self.store.glance(baby_name, now);
+        Ok(serde_json::to_string(&glance).unwrap_or_else(|_| "{}".to_string()))
+    }
 
-    pub fn report(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, String> {
-        let start = parse_timestamp(&format!("{}T00:00:00", start_date))?;
-        let end = parse_timestamp(&format!("{}T00:00:00", end_date))?;
-        let reports = self.store.report(baby_name, start, end);
-        Ok(serde_json::to_string(&reports).unwrap_or_else(|_| "[]".to_string()))
+    /// Whole minutes since the last diaper, or `None` when there is none yet.
+    pub fn minutes_since_last_dejection(&self, baby_name: Option<&str>, now: &str) -> Result<Option<i64>, TrackerError> {
+        let now = self.read_timestamp(now)?;
+        Ok(self.store.time_since_last_dejection(baby_name, now).map(|d| d.num_minutes()))
     }
-}
 
-pub fn parse_timestamp(s: &str) -> Result<NaiveDateTime, String> {
-    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
-        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M"))
-        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
-        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M"))
-        .map_err(|_| format!("Invalid timestamp: '{}'. Use YYYY-MM-DDTHH:MM:SS", s))
-}
+    /// Whole minutes since the last weigh-in, or `None` when there is none yet.
+    pub fn minutes_since_last_weight(&self, baby_name: Option<&str>, now: &str) -> Result<Option<i64>, TrackerError> {
+        let now = self.read_timestamp(now)?;
+        Ok(self.store.time_since_last_weight(baby_name, now).map(|d| d.num_minutes()))
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Day summary, last feeding, predicted next feeding and latest weight as
+    /// one JSON object.
+    pub fn dashboard(&self, baby_name: Option<&str>, now: &str) -> Result<String, TrackerError> {
+        let now = self.read_timestamp(now)?;
+        let dashboard = self.store.dashboard(baby_name, now);
+        Ok(serde_json::to_string(&dashboard).unwrap_or_else(|_| "{}".to_string()))
+    }
 
-    #[test]
-    fn add_and_list_feeding() {
-        let mut t = Tracker::new();
-        let id = t.add_feeding("Emma", "bottle", Some(120.0), None, None, "2026-02-15T08:00:00").unwrap();
-        assert_eq!(id, 1);
-        let json = t.timeline_for_day(None, "2026-02-15").unwrap();
-        assert!(json.contains("bottle"));
+    // --- Duplicate ---
+
+    pub fn duplicate_feeding(&mut self, id: u64, timestamp: Option<&str>) -> Result<Option<u64>, TrackerError> {
+        let ts = timestamp.map(|s| self.read_timestamp(s)).transpose()?;
+        self.store.duplicate_feeding(id, ts)
     }
 
-    #[test]
-    fn add_validates_type() {
-        let mut t = Tracker::new();
-        assert!(t.add_feeding("Emma", "juice", None, None, None, "2026-02-15T08:00:00").is_err());
+    pub fn duplicate_dejection(&mut self, id: u64, timestamp: Option<&str>) -> Result<Option<u64>, TrackerError> {
+        let ts = timestamp.map(|s| self.read_timestamp(s)).transpose()?;
+        self.store.duplicate_dejection(id, ts)
     }
 
-    #[test]
-    fn add_validates_name() {
-        let mut t = Tracker::new();
-        assert!(t.add_feeding("", "bottle", None, None, None, "2026-02-15T08:00:00").is_err());
+    pub fn duplicate_weight(&mut self, id: u64, timestamp: Option<&str>) -> Result<Option<u64>, TrackerError> {
+        let ts = timestamp.map(|s| self.read_timestamp(s)).transpose()?;
+        self.store.duplicate_weight(id, ts)
     }
 
-    #[test]
-    fn add_validates_timestamp() {
-        let mut t = Tracker::new();
-        assert!(t.add_feeding("Emma", "bottle", None, None, None, "not-a-date").is_err());
+    /// Seeds `to_date` with `from_date`'s feedings at the same times; JSON array
+    /// of the new ids.
+    pub fn clone_day(&mut self, baby_name: Option<&str>, from_date: &str, to_date: &str) -> Result<String, TrackerError> {
+        let from = parse_date(from_date)?.date();
+        let to = parse_date(to_date)?.date();
+        let ids = self.store.clone_day(baby_name, from, to)?;
+        Ok(serde_json::to_string(&ids).unwrap_or_else(|_| "[]".to_string()))
     }
 
-    #[test]
-    fn delete_feeding() {
-        let mut t = Tracker::new();
-        let id = t.add_feeding("Emma", "bottle", None, None, None, "2026-02-15T08:00:00").unwrap();
-        assert!(t.delete_feeding(id));
-        assert!(!t.delete_feeding(id));
+    // --- Data quality ---
+
+    pub fn validate_data(&self, now: &str) -> Result<String, TrackerError> {
+        let now = self.read_timestamp(now)?;
+        let warnings = self.store.validate(now);
+        Ok(serde_json::to_string(&warnings).unwrap_or_else(|_| "[]".to_string()))
     }
 
-    #[test]
-    fn update_feeding() {
-        let mut t = Tracker::new();
-        let id = t.add_feeding("Emma", "bottle", Some(100.0), None, None, "2026-02-15T08:00:00").unwrap();
-        assert!(t.update_feeding(id, "solid", Some(200.0), Some(5), Some("Edited".to_string()), "2026-02-15T09:00:00").unwrap());
-        let json = t.timeline_for_day(None, "2026-02-15").unwrap();
-        assert!(json.contains("solid"));
-        assert!(json.contains("200"));
-        assert!(json.contains("Edited"));
+    /// JSON `[{id, kind}]`, `kind` being the feeding type's wire name.
+    pub fn incomplete_feedings(&self, baby_name: Option<&str>) -> String {
+        let items: Vec<serde_json::Value> = self
+            .store
+            .incomplete_feedings(baby_name)
+            .into_iter()
+            .map(|f| serde_json::json!({ "id": f.id, "kind": f.feeding_type.wire_name() }))
+            .collect();
+        serde_json::to_string(&items).unwrap_or_else(|_| "[]".to_string())
     }
 
-    #[test]
-    fn update_feeding_invalid_type() {
-        let mut t = Tracker::new();
-        let id = t.add_feeding("Emma", "bottle", None, None, None, "2026-02-15T08:00:00").unwrap();
-        assert!(t.update_feeding(id, "juice", None, None, None, "2026-02-15T08:00:00").is_err());
+    // --- Lookup ---
+
+    pub fn get_entry(&self, id: u64) -> String {
+        serde_json::to_string(&self.store.get_entry(id)).unwrap_or_else(|_| "null".to_string())
     }
 
-    // --- Dejections ---
+    /// The stored `Feeding` as JSON (unset fields stay `null`), or `"null"`.
+    pub fn get_feeding_raw(&self, id: u64) -> String {
+        serde_json::to_string(&self.store.get_feeding(id)).unwrap_or_else(|_| "null".to_string())
+    }
 
-    #[test]
-    fn add_dejection() {
-        let mut t = Tracker::new();
-        let id = t.add_dejection("Emma", "poop", Some("Soft".to_string()), "2026-02-15T10:00:00").unwrap();
-        assert_eq!(id, 1);
-        let json = t.timeline_for_day(None, "2026-02-15").unwrap();
-        assert!(json.contains("dejection"));
-        assert!(json.contains("poop"));
+    pub fn get_dejection_raw(&self, id: u64) -> String {
+        serde_json::to_string(&self.store.get_dejection(id)).unwrap_or_else(|_| "null".to_string())
     }
 
-    #[test]
-    fn add_dejection_validates_type() {
-        let mut t = Tracker::new();
-        assert!(t.add_dejection("Emma", "vomit", None, "2026-02-15T10:00:00").is_err());
+    pub fn get_weight_raw(&self, id: u64) -> String {
+        serde_json::to_string(&self.store.get_weight(id)).unwrap_or_else(|_| "null".to_string())
     }
 
-    #[test]
-    fn delete_dejection() {
-        let mut t = Tracker::new();
-        let id = t.add_dejection("Emma", "urine", None, "2026-02-15T10:00:00").unwrap();
-        assert!(t.delete_dejection(id));
-        assert!(!t.delete_dejection(id));
+    pub fn append_note(&mut self, id: u64, text: &str) -> bool {
+        self.store.append_note(id, text)
     }
 
-    #[test]
-    fn update_dejection() {
-        let mut t = Tracker::new();
-        let id = t.add_dejection("Emma", "urine", None, "2026-02-15T10:00:00").unwrap();
-        assert!(t.update_dejection(id, "poop", Some("Changed".to_string()), "2026-02-15T11:00:00").unwrap());
-        let json = t.timeline_for_day(None, "2026-02-15").unwrap();
-        assert!(json.contains("poop"));
-        assert!(json.contains("Changed"));
+    // --- Counts by kind (date range) ---
+
+    pub fn counts_by_kind(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let counts = self.store.counts_by_kind(baby_name, start, end);
+        Ok(serde_json::to_string(&counts).unwrap_or_else(|_| "{}".to_string()))
     }
 
-    // --- Weight ---
+    // --- Fussiness ---
 
-    #[test]
-    fn add_weight() {
-        let mut t = Tracker::new();
-        let id = t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
-        assert_eq!(id, 1);
-        let json = t.timeline_for_day(None, "2026-02-15").unwrap();
-        assert!(json.contains("weight"));
-        assert!(json.contains("3.5"));
+    pub fn avg_fussiness(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<Option<f64>, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        Ok(self.store.avg_fussiness(baby_name, start, end))
     }
 
-    #[test]
-    fn add_weight_validates() {
-        let mut t = Tracker::new();
-        assert!(t.add_weight("", 3.5, None, "2026-02-15T08:00:00").is_err());
-        assert!(t.add_weight("Emma", 0.0, None, "2026-02-15T08:00:00").is_err());
-        assert!(t.add_weight("Emma", 3.5, None, "bad-date").is_err());
+    // --- Tracking span ---
+
+    pub fn tracking_span(&self, baby_name: Option<&str>) -> String {
+        match self.store.tracking_span(baby_name) {
+            Some((start, end)) => serde_json::json!({
+                "start": start,
+                "end": end,
+                "days": self.store.tracking_days(baby_name),
+            })
+            .to_string(),
+            None => "null".to_string(),
+        }
     }
 
-    #[test]
-    fn update_weight() {
-        let mut t = Tracker::new();
-        let id = t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
-        assert!(t.update_weight(id, 4.0, Some("Grew!".to_string()), "2026-02-15T10:00:00").unwrap());
-        let json = t.timeline_for_day(None, "2026-02-15").unwrap();
-        assert!(json.contains("4.0"));
-        assert!(json.contains("Grew!"));
+    pub fn logging_streak(&self, baby_name: Option<&str>, date: &str) -> Result<u32, TrackerError> {
+        let up_to = parse_date(date)?.date();
+        Ok(self.store.logging_streak(baby_name, up_to))
     }
 
-    #[test]
-    fn delete_weight() {
-        let mut t = Tracker::new();
-        let id = t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
-        assert!(t.delete_weight(id));
-        assert!(!t.delete_weight(id));
+    pub fn missing_days(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?.date();
+        let end = parse_date(end_date)?.date();
+        let days = self.store.missing_days(baby_name, start, end);
+        Ok(serde_json::to_string(&days).unwrap_or_else(|_| "[]".to_string()))
     }
 
     // --- Timeline ---
 
-    #[test]
-    fn timeline_merges_all_types() {
-        let mut t = Tracker::new();
-        t.add_feeding("Emma", "bottle", Some(120.0), None, None, "2026-02-15T08:00:00").unwrap();
-        t.add_dejection("Emma", "poop", None, "2026-02-15T09:00:00").unwrap();
-        t.add_weight("Emma", 3.5, None, "2026-02-15T10:00:00").unwrap();
-        t.add_feeding("Emma", "bl", None, Some(15), None, "2026-02-15T11:00:00").unwrap();
+    /// Timestamps are emitted as `YYYY-MM-DDTHH:MM:SS`. Without `include_seconds`
+    /// they are truncated to the minute, so the seconds always read `:00`.
+    pub fn timeline_for_day(&self, baby_name: Option<&str>, date: &str, include_seconds: bool) -> Result<String, TrackerError> {
+        let day_start = parse_date(date)?;
+        let day_end = day_start + chrono::Duration::days(1);
+        let entries = self.store.timeline_for_day(baby_name, day_start, day_end);
+        Ok(timeline_json(entries, include_seconds))
+    }
 
-        let json = t.timeline_for_day(None, "2026-02-15").unwrap();
-        let entries: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
-        assert_eq!(entries.len(), 4);
-        assert_eq!(entries[0]["kind"], "feeding");
-        assert_eq!(entries[1]["kind"], "dejection");
-        assert_eq!(entries[2]["kind"], "weight");
-        assert_eq!(entries[3]["kind"], "feeding");
+    /// Day timeline where each feeding carries a `cumulative_ml` running total.
+    /// `include_seconds` works as in `timeline_for_day`.
+    pub fn timeline_for_day_cumulative(&self, baby_name: Option<&str>, date: &str, include_seconds: bool) -> Result<String, TrackerError> {
+        let day_start = parse_date(date)?;
+        let day_end = day_start + chrono::Duration::days(1);
+        let entries = self.store.timeline_for_day_cumulative(baby_name, day_start, day_end);
+        Ok(timeline_json(entries, include_seconds))
     }
 
-    #[test]
-    fn export_and_load_with_all_types() {
-        let mut t = Tracker::new();
-        t.add_feeding("Emma", "bl", None, Some(15), None, "2026-02-15T08:00:00").unwrap();
-        t.add_dejection("Emma", "poop", None, "2026-02-15T09:00:00").unwrap();
-        t.add_weight("Emma", 3.5, None, "2026-02-15T10:00:00").unwrap();
+    // --- Summary (day-bounded) ---
 
-        let json = t.export_data();
-        let restored = Tracker::from_json(&json).unwrap();
-        let tl = restored.timeline_for_day(None, "2026-02-15").unwrap();
-        assert!(tl.contains("feeding"));
-        assert!(tl.contains("dejection"));
-        assert!(tl.contains("weight"));
+    pub fn get_summary(&self, baby_name: Option<&str>, date: &str) -> Result<String, TrackerError> {
+        let since = parse_date(date)?;
+        let until = since + chrono::Duration::days(1);
+        let summary = self.store.summary(baby_name, since, until);
+        Ok(serde_json::to_string(&summary).unwrap_or_else(|_| "{}".to_string()))
     }
 
-    // --- Summary (day-bounded) ---
+    /// Like `get_summary`, with all four feeding types in `by_type` even at zero.
+    pub fn get_summary_full(&self, baby_name: Option<&str>, date: &str) -> Result<String, TrackerError> {
+        let since = parse_date(date)?;
+        let until = since + chrono::Duration::days(1);
+        let summary = self.store.summary_full(baby_name, since, until);
+        Ok(serde_json::to_string(&summary).unwrap_or_else(|_| "{}".to_string()))
+    }
 
-    #[test]
-    fn summary_is_day_bounded() {
-        let mut t = Tracker::new();
-        t.add_feeding("Emma", "bottle", Some(100.0), None, None, "2026-02-14T20:00:00").unwrap();
-        t.add_feeding("Emma", "bottle", Some(120.0), None, None, "2026-02-15T08:00:00").unwrap();
-        t.add_dejection("Emma", "urine", None, "2026-02-15T09:00:00").unwrap();
-        t.add_dejection("Emma", "poop", None, "2026-02-15T10:00:00").unwrap();
-        t.add_weight("Emma", 3.5, None, "2026-02-15T11:00:00").unwrap();
-        t.add_feeding("Emma", "bottle", Some(90.0), None, None, "2026-02-16T06:00:00").unwrap();
+    pub fn get_summary_per_baby(&self, date: &str) -> Result<String, TrackerError> {
+        let since = parse_date(date)?;
+        let until = since + chrono::Duration::days(1);
+        let per_baby: BTreeMap<String, _> = self.store.summary_per_baby(since, until).into_iter().collect();
+        Ok(serde_json::to_string(&per_baby).unwrap_or_else(|_| "{}".to_string()))
+    }
 
-        let s = t.get_summary(None, "2026-02-15").unwrap();
-        assert!(s.contains("\"total_feedings\":1"));
-        assert!(s.contains("\"total_ml\":120"));
-        assert!(s.contains("\"total_urine\":1"));
-        assert!(s.contains("\"total_poop\":1"));
-        assert!(s.contains("\"latest_weight_kg\":3.5"));
+    /// Unknown locales fall back to English.
+    pub fn set_locale(&mut self, locale: &str) {
+        self.store.set_locale(Locale::parse(locale))
     }
 
-    // --- Report ---
+    /// When on, unknown feeding type names are stored as custom types instead of
+    /// being rejected.
+    pub fn set_lenient_feeding_types(&mut self, lenient: bool) {
+        self.store.set_lenient_feeding_types(lenient)
+    }
 
-    #[test]
-    fn report_returns_per_day_data() {
-        let mut t = Tracker::new();
-        t.add_feeding("Emma", "bottle", Some(120.0), None, None, "2026-02-14T08:00:00").unwrap();
-        t.add_feeding("Emma", "bl", None, Some(15), None, "2026-02-15T10:00:00").unwrap();
+    fn parse_feeding_type(&self, s: &str) -> Result<FeedingType, TrackerError> {
+        if self.store.lenient_feeding_types() {
+            FeedingType::parse_lenient(s)
+        } else {
+            FeedingType::parse(s)
+        }
+    }
 
-        let r = t.report(None, "2026-02-14", "2026-02-16").unwrap();
-        let days: Vec<serde_json::Value> = serde_json::from_str(&r).unwrap();
-        assert_eq!(days.len(), 2);
-        assert_eq!(days[0]["date"], "2026-02-14");
-        assert_eq!(days[0]["total_feedings"], 1);
-        assert_eq!(days[0]["total_ml"], 120.0);
-        assert_eq!(days[1]["date"], "2026-02-15");
-        assert_eq!(days[1]["total_feedings"], 1);
-        assert_eq!(days[1]["total_minutes"], 15);
+    /// `"metric"` (the default) or `"imperial"` for `summary_text`.
+    pub fn set_unit_system(&mut self, units: &str) -> Result<(), TrackerError> {
+        self.store.set_unit_system(UnitSystem::parse(units)?);
+        Ok(())
     }
 
-    #[test]
-    fn parse_various_formats() {
-        assert!(parse_timestamp("2026-02-15T08:00:00").is_ok());
-        assert!(parse_timestamp("2026-02-15T08:00").is_ok());
-        assert!(parse_timestamp("2026-02-15 08:00:00").is_ok());
-        assert!(parse_timestamp("2026-02-15 08:00").is_ok());
-        assert!(parse_timestamp("bad").is_err());
+    /// When on, every timestamp argument must be a full `YYYY-MM-DDTHH:MM:SS`
+    /// (see `parse_timestamp_strict`); shorter, zoned and relative forms are
+    /// rejected. Saved with the data.
+    pub fn set_strict_timestamps(&mut self, strict: bool) {
+        self.store.set_strict_timestamps(strict)
+    }
+
+    /// strftime pattern, e.g. `%d/%m/%Y %H:%M`, for `format_timestamp`,
+    /// `summary_text` and the timestamps in `validate` messages. Errors if the
+    /// pattern cannot format a timestamp.
+    pub fn set_display_format(&mut self, fmt: &str) -> Result<(), TrackerError> {
+        self.store.set_display_format(fmt.to_string())
+    }
+
+    /// Labels shown instead of "wet"/"dirty" and on timeline dejections; pass an
+    /// empty string to keep the default for that type.
+    pub fn set_dejection_labels(&mut self, urine: &str, poop: &str) {
+        self.store.set_dejection_labels(urine.to_string(), poop.to_string())
+    }
+
+    /// Formats an ISO timestamp with the display format.
+    pub fn format_timestamp(&self, timestamp: &str) -> Result<String, TrackerError> {
+        Ok(self.store.format_timestamp(self.read_timestamp(timestamp)?))
+    }
+
+    /// One-line recap for notifications, e.g.
+    /// `Emma: 7 feeds (480 ml, 45 min nursing), 5 wet, 2 dirty, 3.6 kg. Last logged 2026-02-15 21:40.`
+    /// Clauses with a zero value are left out; the time of the day's latest entry
+    /// uses the display format. Wording follows the store's locale
+    /// and units its unit system (`16 oz`, `7 lb 11 oz` when imperial).
+    pub fn summary_text(&self, baby_name: Option<&str>, date: &str) -> Result<String, TrackerError> {
+        let since = parse_date(date)?;
+        let until = since + chrono::Duration::days(1);
+        let summary = self.store.summary(baby_name, since, until);
+        let words = SummaryWords::for_locale(self.store.locale());
+        let units = self.store.unit_system();
+
+        let nursing_minutes: u32 = summary
+            .by_type_detail
+            .iter()
+            .filter(|(ft, ..)| matches!(ft, FeedingType::BreastLeft | FeedingType::BreastRight))
+            .map(|(.., minutes)| minutes)
+            .sum();
+
+        let mut clauses = Vec::new();
+        if summary.total_feedings > 0 {
+            let mut feeds = counted(summary.total_feedings, words.feed);
+            let mut details = Vec::new();
+            if summary.total_ml > 0.0 {
+                details.push(units.format_volume(summary.total_ml));
+            }
+            if nursing_minutes > 0 {
+                details.push(format!("{} {}", nursing_minutes, words.nursing));
+            }
+            if !details.is_empty() {
+                feeds.push_str(&format!(" ({})", details.join(", ")));
+            }
+            clauses.push(feeds);
+        }
+        let label = |dt: DejectionType, default| self.store.dejection_label(&dt).map_or(default, |l| (l, l));
+        if summary.total_urine > 0 {
+            clauses.push(counted(summary.total_urine, label(DejectionType::Urine, words.wet)));
+        }
+        if summary.total_poop > 0 {
+            clauses.push(counted(summary.total_poop, label(DejectionType::Poop, words.dirty)));
+        }
+        if let Some(kg) = summary.latest_weight_kg {
+            clauses.push(units.format_weight(kg));
+        }
+
+        let body = if clauses.is_empty() {
+            words.nothing.to_string()
+        } else {
+            clauses.join(", ")
+        };
+        let mut text = match baby_name {
+            Some(name) => format!("{}: {}.", name, body),
+            None => format!("{}.", capitalize_first(&body)),
+        };
+        if let Some(last) = summary.last_event {
+            text.push_str(&format!(" {} {}.", words.last_logged, self.store.format_timestamp(last)));
+        }
+        Ok(text)
+    }
+
+    // --- Daily goal ---
+
+    pub fn set_daily_goal_ml(&mut self, goal_ml: f64) {
+        self.store.set_daily_goal_ml(goal_ml)
+    }
+
+    /// `{"consumed_ml": .., "goal_ml": ..}` for the day, or `"null"` without a goal.
+    pub fn goal_progress(&self, baby_name: Option<&str>, date: &str) -> Result<String, TrackerError> {
+        let day = parse_date(date)?.date();
+        Ok(match self.store.goal_progress(baby_name, day) {
+            Some((consumed, goal)) => serde_json::json!({ "consumed_ml": consumed, "goal_ml": goal }).to_string(),
+            None => "null".to_string(),
+        })
+    }
+
+    // --- Intake per kg ---
+
+    pub fn ml_per_kg_per_day(&self, baby_name: Option<&str>, date: &str) -> Result<Option<f64>, TrackerError> {
+        let day = parse_date(date)?.date();
+        Ok(self.store.ml_per_kg_per_day(baby_name, day))
+    }
+
+    // --- Baby metadata ---
+
+    /// `birthdate` is `YYYY-MM-DD`; a blank colour is stored as none.
+    pub fn set_baby_meta(
+        &mut self,
+        baby_name: &str,
+        color: Option<String>,
+        birthdate: Option<&str>,
+    ) -> Result<(), TrackerError> {
+        if baby_name.trim().is_empty() {
+            return Err(TrackerError::EmptyName);
+        }
+        let meta = BabyMeta {
+            color: color.filter(|c| !c.trim().is_empty()),
+            birthdate: birthdate.map(parse_date).transpose()?.map(|ts| ts.date()),
+        };
+        self.store.set_baby_meta(baby_name, meta);
+        Ok(())
+    }
+
+    /// JSON `{color, birthdate}`, or `"null"` when nothing is stored for the baby.
+    pub fn get_baby_meta(&self, baby_name: &str) -> String {
+        match self.store.baby_meta(baby_name) {
+            Some(meta) => serde_json::to_string(meta).unwrap_or_else(|_| "null".to_string()),
+            None => "null".to_string(),
+        }
+    }
+
+    /// JSON `{weeks, days}` at `timestamp`, or `"null"` without a birthdate or
+    /// before birth.
+    pub fn age_at(&self, baby_name: &str, timestamp: &str) -> Result<String, TrackerError> {
+        let at = self.read_timestamp(timestamp)?;
+        Ok(match self.store.age_at(baby_name, at) {
+            Some((weeks, days)) => serde_json::json!({ "weeks": weeks, "days": days }).to_string(),
+            None => "null".to_string(),
+        })
+    }
+
+    // --- Sync ---
+
+    pub fn entries_by_id(&self) -> String {
+        serde_json::to_string(&self.store.entries_by_id()).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    // --- Lifetime totals ---
+
+    pub fn lifetime_totals(&self, baby_name: Option<&str>) -> String {
+        let totals = self.store.lifetime_totals(baby_name);
+        serde_json::to_string(&totals).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    // --- Report (date range) ---
+
+    pub fn report(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let reports = self.store.report(baby_name, start, end);
+        Ok(serde_json::to_string(&reports).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    pub fn report_by_daypart(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let reports = self.store.report_by_daypart(baby_name, start, end);
+        Ok(serde_json::to_string(&reports).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    // --- Average daily volume (date range) ---
+
+    pub fn avg_daily_ml(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<f64, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        Ok(self.store.avg_daily_ml(baby_name, start, end))
+    }
+
+    // --- Weight velocity ---
+
+    pub fn weight_velocity(&self, baby_name: Option<&str>) -> Option<f64> {
+        self.store.weight_velocity(baby_name)
+    }
+
+    /// JSON `[[date, kg], ...]`, the last weigh-in of each day in the range.
+    pub fn weight_series(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let series = self.store.weight_series(baby_name, start, end);
+        Ok(serde_json::to_string(&series).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    // --- Calorie estimate (date range) ---
+
+    pub fn set_calorie_factors(&mut self, kcal_per_ml: f64, kcal_per_solid: f64) {
+        self.store.set_calorie_factors(kcal_per_ml, kcal_per_solid)
+    }
+
+    pub fn estimated_calories(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<f64, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        Ok(self.store.estimated_calories(baby_name, start, end))
+    }
+
+    // --- Hourly histogram (date range) ---
+
+    pub fn feeding_hour_histogram(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let buckets = self.store.feeding_hour_histogram(baby_name, start, end);
+        Ok(serde_json::to_string(&buckets).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    /// JSON `[[hour, avg_ml], ...]` for all 24 hours; see `Store::typical_day`.
+    pub fn typical_day(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let hours = self.store.typical_day(baby_name, start, end);
+        Ok(serde_json::to_string(&hours).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    /// JSON `[[label, count], ...]` of nursing durations; see
+    /// `Store::nursing_duration_buckets`.
+    pub fn nursing_duration_buckets(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let buckets = self.store.nursing_duration_buckets(baby_name, start, end);
+        Ok(serde_json::to_string(&buckets).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    // --- Next feeding prediction ---
+
+    pub fn predict_next_feeding(&self, baby_name: Option<&str>, now: &str) -> Result<Option<String>, TrackerError> {
+        let now = self.read_timestamp(now)?;
+        Ok(self
+            .store
+            .predict_next_feeding(baby_name, now)
+            .map(|ts| ts.format("%Y-%m-%dT%H:%M:%S").to_string()))
+    }
+
+    // --- Typical bottle size ---
+
+    pub fn typical_bottle_ml(&self, baby_name: Option<&str>) -> Option<f64> {
+        self.store.typical_bottle_ml(baby_name)
+    }
+
+    // --- Milk sources ---
+
+    pub fn sources_used(&self, baby_name: Option<&str>) -> String {
+        serde_json::to_string(&self.store.sources_used(baby_name)).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    // --- Cluster feeding ---
+
+    pub fn cluster_windows(&self, baby_name: Option<&str>, min_count: usize, within_minutes: u32) -> String {
+        let clusters = self.store.cluster_windows(baby_name, min_count, within_minutes);
+        serde_json::to_string(&clusters).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// JSON `[[start, minutes], ...]`; see `Store::merged_nursing_sessions`.
+    pub fn merged_nursing_sessions(
+        &self,
+        baby_name: Option<&str>,
+        start_date: &str,
+        end_date: &str,
+        gap_minutes: u32,
+    ) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let sessions = self.store.merged_nursing_sessions(baby_name, start, end, gap_minutes);
+        Ok(serde_json::to_string(&sessions).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    // --- Diaper gaps (date range) ---
+
+    pub fn diaper_gaps(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let gaps = self.store.diaper_gaps(baby_name, start, end);
+        Ok(serde_json::to_string(&gaps).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    // --- Breast balance (date range) ---
+
+    pub fn breast_balance(&self, baby_name: Option<&str>, start_date: &str, end_date: &str) -> Result<String, TrackerError> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+        let (left, right) = self.store.breast_balance(baby_name, start, end);
+        Ok(serde_json::json!({ "left_minutes": left, "right_minutes": right }).to_string())
+    }
+
+    /// `"breast-left"` or `"breast-right"`, whichever side is due next.
+    pub fn next_breast(&self, baby_name: Option<&str>) -> Option<String> {
+        self.store
+            .next_breast(baby_name)
+            .map(|ft| ft.wire_name().to_string())
+    }
+}
+
+/// Phrases used by `Tracker::summary_text`; counted nouns are (singular, plural).
+struct SummaryWords {
+    feed: (&'static str, &'static str),
+    nursing: &'static str,
+    wet: (&'static str, &'static str),
+    dirty: (&'static str, &'static str),
+    nothing: &'static str,
+    /// Precedes the day's latest entry time, shown in the display format.
+    last_logged: &'static str,
+}
+
+impl SummaryWords {
+    fn for_locale(locale: Locale) -> Self {
+        match locale {
+            Locale::En => SummaryWords {
+                feed: ("feed", "feeds"),
+                nursing: "min nursing",
+                wet: ("wet", "wet"),
+                dirty: ("dirty", "dirty"),
+                nothing: "nothing logged",
+                last_logged: "Last logged",
+            },
+            Locale::Es => SummaryWords {
+                feed: ("toma", "tomas"),
+                nursing: "min de pecho",
+                wet: ("mojado", "mojados"),
+                dirty: ("sucio", "sucios"),
+                nothing: "sin registros",
+                last_logged: "Último registro",
+            },
+        }
+    }
+}
+
+// Payloads for `Tracker::add_entry`, one field per `add_*` argument.
+
+#[derive(Deserialize)]
+struct FeedingInput {
+    baby_name: String,
+    feeding_type: String,
+    amount_ml: Option<f64>,
+    leftover_ml: Option<f64>,
+    duration_minutes: Option<u32>,
+    notes: Option<String>,
+    source: Option<String>,
+    fussiness: Option<u8>,
+    timestamp: String,
+    #[serde(default)]
+    dedupe: bool,
+    now: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DejectionInput {
+    baby_name: String,
+    dejection_type: String,
+    #[serde(default)]
+    rash: bool,
+    notes: Option<String>,
+    timestamp: String,
+    now: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WeightInput {
+    baby_name: String,
+    weight_kg: f64,
+    notes: Option<String>,
+    timestamp: String,
+    now: Option<String>,
+}
+
+fn counted(n: u64, (singular, plural): (&str, &str)) -> String {
+    format!("{} {}", n, if n == 1 { singular } else { plural })
+}
+
+fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Serializes a day timeline, truncating timestamps to the minute unless
+/// `include_seconds` is set.
+fn timeline_json(mut entries: Vec<TimelineEntry>, include_seconds: bool) -> String {
+    if !include_seconds {
+        for e in &mut entries {
+            e.timestamp = e.timestamp.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(e.timestamp);
+        }
+    }
+    serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Splits a comma-separated name list, trimming each name and dropping empty
+/// segments, so `"Emma, Noah,"` is `["Emma", "Noah"]`.
+pub fn split_names(names: &str) -> Vec<String> {
+    names.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+/// Parses a `YYYY-MM-DD` date into the timestamp at the start of that day.
+fn parse_date(date: &str) -> Result<NaiveDateTime, TrackerError> {
+    parse_timestamp(&format!("{}T00:00:00", date))
+}
+
+/// Parses `timestamp`, allowing relative forms when a reference `now` is given.
+fn resolve_timestamp(timestamp: &str, now: Option<&str>) -> Result<NaiveDateTime, TrackerError> {
+    match now {
+        Some(now) => parse_timestamp_rel(timestamp, parse_timestamp(now)?),
+        None => parse_timestamp(timestamp),
+    }
+}
+
+/// Like `parse_timestamp`, but also accepts `now`, `now-30m`, `now+2h` and
+/// similar, resolved against `now`. The library never reads the clock itself.
+pub fn parse_timestamp_rel(s: &str, now: NaiveDateTime) -> Result<NaiveDateTime, TrackerError> {
+    let invalid = || TrackerError::InvalidTimestamp(s.to_string());
+    let lower = s.trim().to_lowercase();
+    let Some(offset) = lower.strip_prefix("now") else {
+        return parse_timestamp(s);
+    };
+    if offset.is_empty() {
+        return Ok(now);
+    }
+    let (sign, rest) = if let Some(rest) = offset.strip_prefix('-') {
+        (-1, rest)
+    } else if let Some(rest) = offset.strip_prefix('+') {
+        (1, rest)
+    } else {
+        return Err(invalid());
+    };
+    let delta = if let Some(minutes) = rest.strip_suffix('m') {
+        chrono::Duration::minutes(minutes.parse::<u32>().map_err(|_| invalid())?.into())
+    } else if let Some(hours) = rest.strip_suffix('h') {
+        chrono::Duration::hours(hours.parse::<u32>().map_err(|_| invalid())?.into())
+    } else {
+        return Err(invalid());
+    };
+    now.checked_add_signed(delta * sign).ok_or_else(invalid)
+}
+
+/// Accepts naive local timestamps and RFC 3339 strings with an offset. The store
+/// holds wall-clock times without a zone, so an offset timestamp keeps the local
+/// time as written (`08:00+02:00` becomes `08:00`) rather than shifting to UTC.
+pub fn parse_timestamp(s: &str) -> Result<NaiveDateTime, TrackerError> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M"))
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M"))
+        .or_else(|_| DateTime::parse_from_rfc3339(s).map(|dt| dt.naive_local()))
+        .map_err(|_| TrackerError::InvalidTimestamp(s.to_string()))
+}
+
+/// Only the full `YYYY-MM-DDTHH:MM:SS` form, for callers that want anything
+/// shorter or zoned rejected rather than guessed at.
+pub fn parse_timestamp_strict(s: &str) -> Result<NaiveDateTime, TrackerError> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").map_err(|_| TrackerError::InvalidTimestamp(s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_list_feeding() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(id, 1);
+        let json = t.timeline_for_day(None, "2026-02-15", true).unwrap();
+        assert!(json.contains("bottle"));
+    }
+
+    #[test]
+    fn add_validates_type() {
+        let mut t = Tracker::new();
+        assert!(t.add_feeding("Emma", "juice", None, None, None, None, None, None, "2026-02-15T08:00:00").is_err());
+    }
+
+    #[test]
+    fn add_validates_name() {
+        let mut t = Tracker::new();
+        assert!(t.add_feeding("", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").is_err());
+    }
+
+    #[test]
+    fn add_validates_timestamp() {
+        let mut t = Tracker::new();
+        assert!(t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "not-a-date").is_err());
+    }
+
+    #[test]
+    fn add_feeding_multi_logs_one_per_name() {
+        let mut t = Tracker::new();
+        let names = vec!["Emma".to_string(), "Noah".to_string()];
+        let ids = t.add_feeding_multi(names, "bl", None, None, Some(15), None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(ids, vec![1, 2]);
+        let noah = t.store.get_feeding(2).unwrap();
+        assert_eq!((noah.baby_name.as_str(), noah.duration_minutes), ("Noah", Some(15)));
+
+        let bad = vec!["Emma".to_string(), " ".to_string()];
+        let err = t.add_feeding_multi(bad, "bottle", None, None, None, None, None, None, "2026-02-15T09:00:00").unwrap_err();
+        assert_eq!(err, TrackerError::EmptyName);
+        assert_eq!(t.store.entry_count(), 2);
+    }
+
+    #[test]
+    fn add_feeding_multi_adds_nothing_when_ids_run_out() {
+        let json = format!(r#"{{"feedings":[],"next_id":{}}}"#, u64::MAX - 1);
+        let mut t = Tracker::from_json(&json).unwrap();
+        let names = vec!["Emma".to_string(), "Noah".to_string()];
+        let err = t.add_feeding_multi(names, "bottle", Some(90.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap_err();
+        assert_eq!(err, TrackerError::IdsExhausted);
+        assert_eq!(t.store.entry_count(), 0);
+    }
+
+    #[test]
+    fn split_names_trims_and_drops_empty_segments() {
+        assert_eq!(split_names("Emma,Noah,"), vec!["Emma", "Noah"]);
+        assert_eq!(split_names(" Emma , ,Noah "), vec!["Emma", "Noah"]);
+        assert!(split_names(",").is_empty());
+    }
+
+    #[test]
+    fn add_dedupe_rejects_double_tap() {
+        let mut t = Tracker::new();
+        let bottle_at = |ts: &str| {
+            format!(r#"{{"baby_name":"Emma","feeding_type":"bottle","amount_ml":120,"timestamp":"{}","dedupe":true}}"#, ts)
+        };
+        t.add_entry("feeding", &bottle_at("2026-02-15T08:00:00")).unwrap();
+        let err = t.add_entry("feeding", &bottle_at("2026-02-15T08:00:30")).unwrap_err();
+        assert_eq!(err, TrackerError::DuplicateFeeding);
+        assert!(t.add_entry("feeding", &bottle_at("2026-02-15T08:05:00")).is_ok());
+    }
+
+    #[test]
+    fn add_without_dedupe_allows_duplicates() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").is_ok());
+    }
+
+    #[test]
+    fn patch_feeding_keeps_unset_fields() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", Some(100.0), None, None, Some("Burped".to_string()), None, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.patch_feeding(id, None, None, None, None, None, None, None, Some("2026-02-15T08:20:00")).unwrap());
+
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(id)).unwrap();
+        assert_eq!(entry["timestamp"], "2026-02-15T08:20:00");
+        assert_eq!(entry["subtype"], "bottle");
+        assert_eq!(entry["amount_ml"], 100.0);
+        assert_eq!(entry["notes"], "Burped");
+    }
+
+    #[test]
+    fn patch_feeding_validates_given_fields() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", Some(100.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.patch_feeding(id, Some("juice"), None, None, None, None, None, None, None).is_err());
+        assert!(t.patch_feeding(id, None, Some(-1.0), None, None, None, None, None, None).is_err());
+        assert!(t.patch_feeding(id, None, None, Some(150.0), None, None, None, None, None).is_err());
+        assert!(t.patch_feeding(id, None, None, None, None, None, None, None, Some("bad")).is_err());
+        assert!(!t.patch_feeding(999, None, None, None, None, None, None, None, None).unwrap());
+    }
+
+    #[test]
+    fn add_quick_feeding_uses_now_only() {
+        let mut t = Tracker::new();
+        let id = t.add_quick_feeding("Emma", "bl", "2026-02-15T03:12:00").unwrap();
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(id)).unwrap();
+        assert_eq!(entry["timestamp"], "2026-02-15T03:12:00");
+        assert_eq!(entry["subtype"], "breast-left");
+        assert!(entry["amount_ml"].is_null());
+        assert!(entry["duration_minutes"].is_null());
+        assert!(entry["notes"].is_null());
+
+        assert!(t.add_quick_feeding("", "bl", "2026-02-15T03:12:00").is_err());
+        assert!(t.add_quick_feeding("Emma", "juice", "2026-02-15T03:12:00").is_err());
+    }
+
+    #[test]
+    fn set_volume_rounding_rounds_new_feedings() {
+        let mut t = Tracker::new();
+        t.set_volume_rounding(5.0);
+        let id = t.add_feeding("Emma", "bottle", Some(62.5), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(id)).unwrap();
+        assert_eq!(entry["amount_ml"], 60.0);
+    }
+
+    #[test]
+    fn set_name_matching_modes() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(100.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let count = |t: &Tracker| {
+            serde_json::from_str::<Vec<serde_json::Value>>(&t.timeline_for_day(Some("emma"), "2026-02-15", true).unwrap())
+                .unwrap()
+                .len()
+        };
+        assert_eq!(count(&t), 0);
+        t.set_name_matching("case-insensitive").unwrap();
+        assert_eq!(count(&t), 1);
+        t.set_name_matching("exact").unwrap();
+        assert_eq!(count(&t), 0);
+        assert!(t.set_name_matching("fuzzy").is_err());
+    }
+
+    #[test]
+    fn delete_feeding() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.delete_feeding(id));
+        assert!(!t.delete_feeding(id));
+    }
+
+    #[test]
+    fn update_feeding() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", Some(100.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.update_feeding(id, "solid", Some(200.0), None, Some(5), Some("Edited".to_string()), None, None, "2026-02-15T09:00:00").unwrap());
+        let json = t.timeline_for_day(None, "2026-02-15", true).unwrap();
+        assert!(json.contains("solid"));
+        assert!(json.contains("200"));
+        assert!(json.contains("Edited"));
+    }
+
+    #[test]
+    fn update_feeding_reporting_same_values() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", Some(100.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let update = |t: &mut Tracker, id, ml| t.update_feeding_reporting(id, "b", Some(ml), None, None, None, None, None, "2026-02-15T08:00:00");
+        assert_eq!(update(&mut t, id, 100.0).unwrap(), Some(false));
+        assert_eq!(update(&mut t, id, 110.0).unwrap(), Some(true));
+        assert_eq!(update(&mut t, 999, 110.0).unwrap(), None);
+    }
+
+    #[test]
+    fn update_feeding_invalid_type() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.update_feeding(id, "juice", None, None, None, None, None, None, "2026-02-15T08:00:00").is_err());
+    }
+
+    // --- Dejections ---
+
+    #[test]
+    fn add_dejection() {
+        let mut t = Tracker::new();
+        let id = t.add_dejection("Emma", "poop", false, Some("Soft".to_string()), "2026-02-15T10:00:00").unwrap();
+        assert_eq!(id, 1);
+        let json = t.timeline_for_day(None, "2026-02-15", true).unwrap();
+        assert!(json.contains("dejection"));
+        assert!(json.contains("poop"));
+    }
+
+    #[test]
+    fn add_dejection_validates_type() {
+        let mut t = Tracker::new();
+        assert!(t.add_dejection("Emma", "vomit", false, None, "2026-02-15T10:00:00").is_err());
+    }
+
+    #[test]
+    fn delete_dejection() {
+        let mut t = Tracker::new();
+        let id = t.add_dejection("Emma", "urine", false, None, "2026-02-15T10:00:00").unwrap();
+        assert!(t.delete_dejection(id));
+        assert!(!t.delete_dejection(id));
+    }
+
+    #[test]
+    fn update_dejection() {
+        let mut t = Tracker::new();
+        let id = t.add_dejection("Emma", "urine", false, None, "2026-02-15T10:00:00").unwrap();
+        assert!(t.update_dejection(id, "poop", false, Some("Changed".to_string()), "2026-02-15T11:00:00").unwrap());
+        let json = t.timeline_for_day(None, "2026-02-15", true).unwrap();
+        assert!(json.contains("poop"));
+        assert!(json.contains("Changed"));
+    }
+
+    // --- Weight ---
+
+    #[test]
+    fn add_weight() {
+        let mut t = Tracker::new();
+        let id = t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(id, 1);
+        let json = t.timeline_for_day(None, "2026-02-15", true).unwrap();
+        assert!(json.contains("weight"));
+        assert!(json.contains("3.5"));
+    }
+
+    #[test]
+    fn add_weight_validates() {
+        let mut t = Tracker::new();
+        assert!(t.add_weight("", 3.5, None, "2026-02-15T08:00:00").is_err());
+        assert!(t.add_weight("Emma", 0.0, None, "2026-02-15T08:00:00").is_err());
+        assert!(t.add_weight("Emma", 3.5, None, "bad-date").is_err());
+    }
+
+    #[test]
+    fn update_weight() {
+        let mut t = Tracker::new();
+        let id = t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.update_weight(id, 4.0, Some("Grew!".to_string()), "2026-02-15T10:00:00").unwrap());
+        let json = t.timeline_for_day(None, "2026-02-15", true).unwrap();
+        assert!(json.contains("4.0"));
+        assert!(json.contains("Grew!"));
+    }
+
+    #[test]
+    fn delete_weight() {
+        let mut t = Tracker::new();
+        let id = t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.delete_weight(id));
+        assert!(!t.delete_weight(id));
+    }
+
+    #[test]
+    fn add_entry_dispatches_feeding() {
+        let mut t = Tracker::new();
+        let out = t
+            .add_entry("feeding", r#"{"baby_name":"Emma","feeding_type":"bottle","amount_ml":120,"timestamp":"2026-02-15T08:00:00"}"#)
+            .unwrap();
+        assert_eq!(out, r#"{"id":1,"kind":"feeding"}"#);
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(1)).unwrap();
+        assert_eq!(entry["amount_ml"], 120.0);
+    }
+
+    #[test]
+    fn add_entry_dispatches_weight() {
+        let mut t = Tracker::new();
+        let out = t
+            .add_entry("weight", r#"{"baby_name":"Emma","weight_kg":3.5,"timestamp":"2026-02-15T08:00:00"}"#)
+            .unwrap();
+        assert_eq!(out, r#"{"id":1,"kind":"weight"}"#);
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(1)).unwrap();
+        assert_eq!(entry["weight_kg"], 3.5);
+    }
+
+    #[test]
+    fn add_entry_rejects_bad_input() {
+        let mut t = Tracker::new();
+        assert_eq!(
+            t.add_entry("sleep", "{}"),
+            Err(TrackerError::InvalidData("unknown entry kind 'sleep'".to_string()))
+        );
+        assert!(matches!(t.add_entry("weight", r#"{"baby_name":"Emma"}"#), Err(TrackerError::InvalidData(_))));
+        assert_eq!(
+            t.add_entry("weight", r#"{"baby_name":"Emma","weight_kg":0,"timestamp":"2026-02-15T08:00:00"}"#),
+            Err(TrackerError::NonPositiveWeight)
+        );
+    }
+
+    #[test]
+    fn latest_of_each_json_with_nulls() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "bl", None, None, Some(10), None, None, None, "2026-02-15T11:00:00").unwrap();
+
+        let latest: serde_json::Value = serde_json::from_str(&t.latest_of_each(None)).unwrap();
+        assert_eq!(latest["feeding"]["feeding_type"], "breast-left");
+        assert!(latest["dejection"].is_null());
+        assert!(latest["weight"].is_null());
+    }
+
+    #[test]
+    fn last_entry_before_json() {
+        let mut t = Tracker::new();
+        assert_eq!(t.last_entry_before(None, "2026-02-15T12:00:00").unwrap(), "null");
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T09:00:00").unwrap();
+        let entry: serde_json::Value = serde_json::from_str(&t.last_entry_before(None, "2026-02-15T12:00:00").unwrap()).unwrap();
+        assert_eq!(entry["kind"], "dejection");
+        assert!(t.last_entry_before(None, "bad").is_err());
+    }
+
+    #[test]
+    fn minutes_since_last_dejection_and_weight() {
+        let mut t = Tracker::new();
+        assert_eq!(t.minutes_since_last_dejection(None, "2026-02-15T09:30:00").unwrap(), None);
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T09:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(t.minutes_since_last_dejection(Some("Emma"), "2026-02-15T09:30:00").unwrap(), Some(30));
+        assert_eq!(t.minutes_since_last_weight(Some("Emma"), "2026-02-15T09:30:00").unwrap(), Some(90));
+        assert_eq!(t.minutes_since_last_weight(Some("Liam"), "2026-02-15T09:30:00").unwrap(), None);
+        assert!(t.minutes_since_last_weight(None, "bad").is_err());
+    }
+
+    #[test]
+    fn glance_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T09:00:00").unwrap();
+        let g: serde_json::Value = serde_json::from_str(&t.glance(None, "2026-02-15T09:30:00").unwrap()).unwrap();
+        assert_eq!(g["feedings_today"], 1);
+        assert_eq!(g["wet_today"], 1);
+        assert_eq!(g["last_feeding"], "2026-02-15T08:00:00");
+        assert_eq!(g["minutes_since_last_feeding"], 90);
+        assert!(g["latest_weight_kg"].is_null());
+        assert!(t.glance(None, "bad").is_err());
+    }
+
+    #[test]
+    fn dashboard_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T05:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let d: serde_json::Value = serde_json::from_str(&t.dashboard(None, "2026-02-15T09:30:00").unwrap()).unwrap();
+        assert_eq!(d["summary"]["total_ml"], 240.0);
+        assert_eq!(d["last_feeding"], "2026-02-15T08:00:00");
+        assert_eq!(d["minutes_since_last_feeding"], 90);
+        assert_eq!(d["next_feeding"], "2026-02-15T11:00:00");
+        assert!(d["latest_weight_kg"].is_null());
+        assert!(t.dashboard(None, "bad").is_err());
+    }
+
+    // --- Duplicate ---
+
+    #[test]
+    fn duplicate_feeding_with_new_timestamp() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let copy = t.duplicate_feeding(id, Some("2026-02-15T11:00:00")).unwrap().unwrap();
+        assert_ne!(copy, id);
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(copy)).unwrap();
+        assert_eq!(entry["amount_ml"], 120.0);
+        assert_eq!(entry["timestamp"], "2026-02-15T11:00:00");
+    }
+
+    #[test]
+    fn duplicate_feeding_validates_timestamp() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert!(t.duplicate_feeding(id, Some("bad")).is_err());
+        assert_eq!(t.duplicate_feeding(999, None).unwrap(), None);
+    }
+
+    #[test]
+    fn clone_day_returns_new_ids() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-14T08:00:00").unwrap();
+        assert_eq!(t.clone_day(Some("Emma"), "2026-02-14", "2026-02-15").unwrap(), "[2]");
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(2)).unwrap();
+        assert_eq!(entry["timestamp"], "2026-02-15T08:00:00");
+        assert!(t.clone_day(None, "2026-02-14", "bad").is_err());
+    }
+
+    // --- Data quality ---
+
+    #[test]
+    fn validate_data_returns_json_messages() {
+        let mut t = Tracker::new();
+        assert_eq!(t.validate_data("2026-02-15T12:00:00").unwrap(), "[]");
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let warnings: Vec<String> = serde_json::from_str(&t.validate_data("2026-02-15T12:00:00").unwrap()).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("bottle with no amount"));
+    }
+
+    #[test]
+    fn incomplete_feedings_json() {
+        let mut t = Tracker::new();
+        assert_eq!(t.incomplete_feedings(None), "[]");
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "breast-left", None, None, None, None, None, None, "2026-02-15T09:00:00").unwrap();
+        t.add_feeding("Emma", "solid", None, None, None, None, None, None, "2026-02-15T10:00:00").unwrap();
+        assert_eq!(t.incomplete_feedings(None), r#"[{"id":1,"kind":"bottle"},{"id":2,"kind":"breast-left"}]"#);
+    }
+
+    // --- Lookup ---
+
+    #[test]
+    fn get_entry_returns_json_or_null() {
+        let mut t = Tracker::new();
+        let id = t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(id)).unwrap();
+        assert_eq!(entry["kind"], "weight");
+        assert_eq!(entry["weight_kg"], 3.5);
+        assert_eq!(t.get_entry(999), "null");
+    }
+
+    #[test]
+    fn raw_getters_keep_missing_and_zero_apart() {
+        let mut t = Tracker::new();
+        let id = t.add_feeding("Emma", "bottle", Some(0.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let raw: serde_json::Value = serde_json::from_str(&t.get_feeding_raw(id)).unwrap();
+        assert_eq!(raw["amount_ml"], 0.0);
+        assert!(raw["duration_minutes"].is_null());
+        assert_eq!(t.get_dejection_raw(id), "null");
+
+        let id = t.add_weight("Emma", 3.5, None, "2026-02-15T09:00:00").unwrap();
+        let raw: serde_json::Value = serde_json::from_str(&t.get_weight_raw(id)).unwrap();
+        assert_eq!(raw["weight_kg"], 3.5);
+        assert!(raw["notes"].is_null());
+        assert_eq!(t.get_feeding_raw(id), "null");
+    }
+
+    // --- Counts by kind ---
+
+    #[test]
+    fn counts_by_kind_json_has_every_kind() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let counts: serde_json::Value = serde_json::from_str(&t.counts_by_kind(None, "2026-02-15", "2026-02-16").unwrap()).unwrap();
+        assert_eq!(counts, serde_json::json!({"dejection": 0, "feeding": 1, "weight": 0}));
+    }
+
+    // --- Tracking span ---
+
+    #[test]
+    fn tracking_span_json() {
+        let mut t = Tracker::new();
+        assert_eq!(t.tracking_span(None), "null");
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-14T20:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-16T08:00:00").unwrap();
+
+        let span: serde_json::Value = serde_json::from_str(&t.tracking_span(None)).unwrap();
+        assert_eq!(span["start"], "2026-02-14T20:00:00");
+        assert_eq!(span["end"], "2026-02-16T08:00:00");
+        assert_eq!(span["days"], 3);
+    }
+
+    #[test]
+    fn weight_series_json_pairs() {
+        let mut t = Tracker::new();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T08:00:00").unwrap();
+        t.add_weight("Emma", 3.6, None, "2026-02-15T20:00:00").unwrap();
+        t.add_weight("Emma", 3.7, None, "2026-02-17T08:00:00").unwrap();
+        assert_eq!(
+            t.weight_series(None, "2026-02-15", "2026-02-20").unwrap(),
+            r#"[["2026-02-15",3.6],["2026-02-17",3.7]]"#
+        );
+        assert!(t.weight_series(None, "bad", "2026-02-20").is_err());
+    }
+
+    #[test]
+    fn logging_streak_up_to_date() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-14T08:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(t.logging_streak(None, "2026-02-15").unwrap(), 2);
+        assert!(t.logging_streak(None, "yesterday").is_err());
+    }
+
+    #[test]
+    fn missing_days_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-14T08:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-16T08:00:00").unwrap();
+        assert_eq!(t.missing_days(None, "2026-02-14", "2026-02-17").unwrap(), r#"["2026-02-15"]"#);
+        assert_eq!(t.missing_days(None, "2026-02-16", "2026-02-17").unwrap(), "[]");
+    }
+
+    // --- Timeline ---
+
+    #[test]
+    fn timeline_seconds_precision() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bl", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "br", None, None, None, None, None, None, "2026-02-15T08:00:30").unwrap();
+
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&t.timeline_for_day(None, "2026-02-15", true).unwrap()).unwrap();
+        assert_eq!(entries[0]["timestamp"], "2026-02-15T08:00:00");
+        assert_eq!(entries[1]["timestamp"], "2026-02-15T08:00:30");
+
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&t.timeline_for_day(None, "2026-02-15", false).unwrap()).unwrap();
+        assert_eq!(entries[0]["timestamp"], "2026-02-15T08:00:00");
+        assert_eq!(entries[1]["timestamp"], "2026-02-15T08:00:00");
+    }
+
+    #[test]
+    fn timeline_merges_all_types() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T09:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T10:00:00").unwrap();
+        t.add_feeding("Emma", "bl", None, None, Some(15), None, None, None, "2026-02-15T11:00:00").unwrap();
+
+        let json = t.timeline_for_day(None, "2026-02-15", true).unwrap();
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0]["kind"], "feeding");
+        assert_eq!(entries[1]["kind"], "dejection");
+        assert_eq!(entries[2]["kind"], "weight");
+        assert_eq!(entries[3]["kind"], "feeding");
+    }
+
+    #[test]
+    fn export_and_load_with_all_types() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bl", None, None, Some(15), None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T09:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T10:00:00").unwrap();
+
+        let json = t.export_data();
+        let restored = Tracker::from_json(&json).unwrap();
+        let tl = restored.timeline_for_day(None, "2026-02-15", true).unwrap();
+        assert!(tl.contains("feeding"));
+        assert!(tl.contains("dejection"));
+        assert!(tl.contains("weight"));
+    }
+
+    #[test]
+    fn delete_range_between_times() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T09:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T12:00:00").unwrap();
+        assert_eq!(t.delete_range(None, "2026-02-15T08:00", "2026-02-15T10:00").unwrap(), 2);
+        assert!(t.delete_range(None, "bad", "2026-02-15T10:00").is_err());
+    }
+
+    #[test]
+    fn clear_resets_tracker() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T09:00:00").unwrap();
+        t.clear();
+        assert_eq!(t.timeline_for_day(None, "2026-02-15", true).unwrap(), "[]");
+        assert_eq!(t.add_weight("Emma", 3.5, None, "2026-02-15T10:00:00").unwrap(), 1);
+    }
+
+    #[test]
+    fn export_and_load_bincode() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T10:00:00").unwrap();
+
+        let restored = Tracker::from_bincode(&t.export_bincode()).unwrap();
+        assert_eq!(restored.export_data(), t.export_data());
+    }
+
+    #[test]
+    fn export_and_load_ndjson() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bl", None, None, Some(15), None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T09:00:00").unwrap();
+
+        let restored = Tracker::from_ndjson(&t.export_ndjson()).unwrap();
+        assert_eq!(restored.timeline_for_day(None, "2026-02-15", true).unwrap(), t.timeline_for_day(None, "2026-02-15", true).unwrap());
+        assert!(Tracker::from_ndjson("not json").is_err());
+    }
+
+    #[test]
+    fn entry_count_and_load_warning() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T09:00:00").unwrap();
+        assert_eq!(t.entry_count(), 2);
+
+        let json = t.export_data();
+        assert_eq!(Tracker::from_json(&json).unwrap().load_warning(), None);
+        let tampered = Tracker::from_json(&json.replace("3.5", "3.6")).unwrap();
+        assert!(tampered.load_warning().is_some());
+    }
+
+    #[test]
+    fn merge_from_other_device() {
+        let mut a = Tracker::new();
+        a.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let mut b = Tracker::from_json(&a.export_data()).unwrap();
+        b.add_dejection("Emma", "poop", false, None, "2026-02-15T09:00:00").unwrap();
+
+        assert_eq!(a.merge_from(&b.export_data()).unwrap(), 1);
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&a.timeline_for_day(None, "2026-02-15", true).unwrap()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(a.merge_from("not json").is_err());
+    }
+
+    #[test]
+    fn diff_with_json() {
+        let mut a = Tracker::new();
+        a.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let mut b = Tracker::from_json(&a.export_data()).unwrap();
+        b.add_dejection("Emma", "poop", false, None, "2026-02-15T09:00:00").unwrap();
+
+        let diff: serde_json::Value = serde_json::from_str(&a.diff_with(&b.export_data()).unwrap()).unwrap();
+        assert_eq!(diff["only_in_self"].as_array().unwrap().len(), 0);
+        assert_eq!(diff["only_in_other"][0]["kind"], "dejection");
+        assert!(a.diff_with("not json").is_err());
+    }
+
+    #[test]
+    fn timeline_cumulative_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(100.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T11:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T12:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(80.0), None, None, None, None, None, "2026-02-15T14:00:00").unwrap();
+
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&t.timeline_for_day_cumulative(None, "2026-02-15", true).unwrap()).unwrap();
+        assert_eq!(entries[0]["cumulative_ml"], 100.0);
+        assert_eq!(entries[1]["cumulative_ml"], 220.0);
+        assert!(entries[2].get("cumulative_ml").is_none());
+        assert_eq!(entries[3]["cumulative_ml"], 300.0);
+
+        let plain = t.timeline_for_day(None, "2026-02-15", true).unwrap();
+        assert!(!plain.contains("cumulative_ml"));
+    }
+
+    #[test]
+    fn timeline_cumulative_minute_precision() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(100.0), Some(30.0), None, None, None, None, "2026-02-15T08:00:42").unwrap();
+
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&t.timeline_for_day_cumulative(None, "2026-02-15", false).unwrap()).unwrap();
+        assert_eq!(entries[0]["timestamp"], "2026-02-15T08:00:00");
+        assert_eq!(entries[0]["cumulative_ml"], 70.0);
+
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&t.timeline_for_day_cumulative(None, "2026-02-15", true).unwrap()).unwrap();
+        assert_eq!(entries[0]["timestamp"], "2026-02-15T08:00:42");
+    }
+
+    // --- Summary (day-bounded) ---
+
+    #[test]
+    fn summary_is_day_bounded() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(100.0), None, None, None, None, None, "2026-02-14T20:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T09:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T10:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T11:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(90.0), None, None, None, None, None, "2026-02-16T06:00:00").unwrap();
+
+        let s = t.get_summary(None, "2026-02-15").unwrap();
+        assert!(s.contains("\"total_feedings\":1"));
+        assert!(s.contains("\"total_ml\":120"));
+        assert!(s.contains("\"total_urine\":1"));
+        assert!(s.contains("\"total_poop\":1"));
+        assert!(s.contains("\"latest_weight_kg\":3.5"));
+    }
+
+    #[test]
+    fn summary_reports_first_and_last_event() {
+        let mut t = Tracker::new();
+        let empty: serde_json::Value = serde_json::from_str(&t.get_summary(None, "2026-02-15").unwrap()).unwrap();
+        assert!(empty["first_event"].is_null());
+        assert!(empty["last_event"].is_null());
+
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T06:00:00").unwrap();
+        t.add_weight("Emma", 3.5, None, "2026-02-15T22:00:00").unwrap();
+        let s: serde_json::Value = serde_json::from_str(&t.get_summary(None, "2026-02-15").unwrap()).unwrap();
+        assert_eq!(s["first_event"], "2026-02-15T06:00:00");
+        assert_eq!(s["last_event"], "2026-02-15T22:00:00");
+    }
+
+    #[test]
+    fn summary_reports_by_type_detail() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "bl", None, None, Some(15), None, None, None, "2026-02-15T10:00:00").unwrap();
+
+        let s: serde_json::Value = serde_json::from_str(&t.get_summary(None, "2026-02-15").unwrap()).unwrap();
+        let detail = s["by_type_detail"].as_array().unwrap();
+        assert_eq!(detail.len(), 2);
+        assert_eq!(detail[0], serde_json::json!(["breast-left", 1, 0.0, 15]));
+        assert_eq!(detail[1], serde_json::json!(["bottle", 1, 120.0, 0]));
+    }
+
+    #[test]
+    fn custom_feeding_types_need_lenient_mode() {
+        let mut t = Tracker::new();
+        assert!(t.add_feeding("Emma", "water", Some(30.0), None, None, None, None, None, "2026-02-15T08:00:00").is_err());
+
+        t.set_lenient_feeding_types(true);
+        let id = t.add_feeding("Emma", "water", Some(30.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        let entry: serde_json::Value = serde_json::from_str(&t.get_entry(id)).unwrap();
+        assert_eq!(entry["subtype"], "water");
+        let s: serde_json::Value = serde_json::from_str(&t.get_summary(None, "2026-02-15").unwrap()).unwrap();
+        assert_eq!(s["by_type"], serde_json::json!([[{ "other": "water" }, 1]]));
+    }
+
+    #[test]
+    fn summary_full_includes_zero_types() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+
+        let s: serde_json::Value = serde_json::from_str(&t.get_summary_full(None, "2026-02-15").unwrap()).unwrap();
+        assert_eq!(
+            s["by_type"],
+            serde_json::json!([["breast-left", 0], ["breast-right", 0], ["bottle", 1], ["solid", 0]])
+        );
+        assert!(t.get_summary_full(None, "bad").is_err());
+    }
+
+    #[test]
+    fn summary_reports_rash_count() {
+        let mut t = Tracker::new();
+        t.add_dejection("Emma", "poop", true, None, "2026-02-15T09:00:00").unwrap();
+        t.add_dejection("Emma", "urine", true, None, "2026-02-15T13:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T15:00:00").unwrap();
+
+        let s = t.get_summary(None, "2026-02-15").unwrap();
+        assert!(s.contains("\"rash_count\":2"));
+    }
+
+    #[test]
+    fn summary_per_baby_keyed_by_name() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Noah", "bottle", Some(90.0), None, None, None, None, None, "2026-02-15T08:05:00").unwrap();
+        t.add_feeding("Liam", "bottle", Some(90.0), None, None, None, None, None, "2026-02-16T08:05:00").unwrap();
+
+        let s: serde_json::Value = serde_json::from_str(&t.get_summary_per_baby("2026-02-15").unwrap()).unwrap();
+        assert_eq!(s.as_object().unwrap().len(), 2);
+        assert_eq!(s["Emma"]["total_ml"], 120.0);
+        assert_eq!(s["Noah"]["total_ml"], 90.0);
+    }
+
+    #[test]
+    fn summary_text_formats_day() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "bl", None, None, Some(20), None, None, None, "2026-02-15T11:00:00").unwrap();
+        t.add_feeding("Emma", "br", None, None, Some(25), None, None, None, "2026-02-15T14:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T09:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T12:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T13:00:00").unwrap();
+        t.add_weight("Emma", 3.6, None, "2026-02-15T10:00:00").unwrap();
+
+        assert_eq!(
+            t.summary_text(Some("Emma"), "2026-02-15").unwrap(),
+            "Emma: 3 feeds (120 ml, 45 min nursing), 2 wet, 1 dirty, 3.6 kg. Last logged 2026-02-15 14:00."
+        );
+    }
+
+    #[test]
+    fn summary_text_omits_zero_clauses() {
+        let mut t = Tracker::new();
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-15").unwrap(), "Emma: nothing logged.");
+
+        t.add_feeding("Emma", "solid", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-15").unwrap(), "Emma: 1 feed. Last logged 2026-02-15 08:00.");
+        assert_eq!(t.summary_text(None, "2026-02-15").unwrap(), "1 feed. Last logged 2026-02-15 08:00.");
+        assert!(t.summary_text(None, "bad").is_err());
+    }
+
+    #[test]
+    fn summary_text_metric_vs_imperial() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(240.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(233.0), None, None, None, None, None, "2026-02-15T12:00:00").unwrap();
+        t.add_weight("Emma", 3.49, None, "2026-02-15T10:00:00").unwrap();
+
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-15").unwrap(), "Emma: 2 feeds (473 ml), 3.49 kg. Last logged 2026-02-15 12:00.");
+        t.set_unit_system("imperial").unwrap();
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-15").unwrap(), "Emma: 2 feeds (16 oz), 7 lb 11 oz. Last logged 2026-02-15 12:00.");
+        t.set_display_format("%d/%m/%Y %H:%M").unwrap();
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-15").unwrap(), "Emma: 2 feeds (16 oz), 7 lb 11 oz. Last logged 15/02/2026 12:00.");
+        assert!(t.set_unit_system("cubits").is_err());
+    }
+
+    #[test]
+    fn format_timestamp_with_custom_display_format() {
+        let mut t = Tracker::new();
+        assert_eq!(t.format_timestamp("2026-02-15T08:05:00").unwrap(), "2026-02-15 08:05");
+        t.set_display_format("%d/%m/%Y %H:%M").unwrap();
+        assert_eq!(t.format_timestamp("2026-02-15T08:05:00").unwrap(), "15/02/2026 08:05");
+        assert!(t.set_display_format("%Q").is_err());
+        assert!(t.format_timestamp("bad").is_err());
+    }
+
+    #[test]
+    fn summary_text_follows_locale() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "bl", None, None, Some(20), None, None, None, "2026-02-15T11:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T09:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T10:00:00").unwrap();
+
+        t.set_locale("es");
+        assert_eq!(
+            t.summary_text(Some("Emma"), "2026-02-15").unwrap(),
+            "Emma: 2 tomas (120 ml, 20 min de pecho), 1 mojado, 1 sucio. Último registro 2026-02-15 11:00."
+        );
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-16").unwrap(), "Emma: sin registros.");
+
+        t.set_locale("xx");
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-16").unwrap(), "Emma: nothing logged.");
+    }
+
+    #[test]
+    fn summary_text_uses_dejection_labels() {
+        let mut t = Tracker::new();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T09:00:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T11:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T10:00:00").unwrap();
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-15").unwrap(), "Emma: 2 wet, 1 dirty. Last logged 2026-02-15 11:00.");
+
+        t.set_dejection_labels("💧", "💩");
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-15").unwrap(), "Emma: 2 💧, 1 💩. Last logged 2026-02-15 11:00.");
+        t.set_dejection_labels("pee", "");
+        assert_eq!(t.summary_text(Some("Emma"), "2026-02-15").unwrap(), "Emma: 2 pee, 1 dirty. Last logged 2026-02-15 11:00.");
+    }
+
+    // --- Daily goal ---
+
+    #[test]
+    fn goal_progress_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(t.goal_progress(None, "2026-02-15").unwrap(), "null");
+        t.set_daily_goal_ml(600.0);
+        let p: serde_json::Value = serde_json::from_str(&t.goal_progress(None, "2026-02-15").unwrap()).unwrap();
+        assert_eq!(p, serde_json::json!({ "consumed_ml": 120.0, "goal_ml": 600.0 }));
+        assert!(t.goal_progress(None, "bad").is_err());
+    }
+
+    // --- Intake per kg ---
+
+    #[test]
+    fn ml_per_kg_per_day_parses_date() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(360.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(t.ml_per_kg_per_day(None, "2026-02-15").unwrap(), None);
+        t.add_weight("Emma", 3.6, None, "2026-02-14T08:00:00").unwrap();
+        assert_eq!(t.ml_per_kg_per_day(None, "2026-02-15").unwrap(), Some(100.0));
+        assert!(t.ml_per_kg_per_day(None, "15/02").is_err());
+    }
+
+    // --- Baby metadata ---
+
+    #[test]
+    fn baby_meta_json() {
+        let mut t = Tracker::new();
+        assert_eq!(t.get_baby_meta("Emma"), "null");
+        t.set_baby_meta("Emma", Some("#ff8800".to_string()), Some("2026-01-20")).unwrap();
+        let meta: serde_json::Value = serde_json::from_str(&t.get_baby_meta("Emma")).unwrap();
+        assert_eq!(meta, serde_json::json!({ "color": "#ff8800", "birthdate": "2026-01-20" }));
+
+        t.set_baby_meta("Noah", Some(" ".to_string()), None).unwrap();
+        assert_eq!(t.get_baby_meta("Noah"), r#"{"color":null,"birthdate":null}"#);
+        assert_eq!(t.set_baby_meta(" ", None, None).unwrap_err(), TrackerError::EmptyName);
+        assert!(t.set_baby_meta("Emma", None, Some("20/01/2026")).is_err());
+    }
+
+    #[test]
+    fn age_at_json() {
+        let mut t = Tracker::new();
+        assert_eq!(t.age_at("Emma", "2026-02-15T08:00:00").unwrap(), "null");
+        t.set_baby_meta("Emma", None, Some("2026-01-20")).unwrap();
+        assert_eq!(t.age_at("Emma", "2026-02-15T08:00:00").unwrap(), r#"{"days":5,"weeks":3}"#);
+        assert_eq!(t.age_at("Emma", "2026-01-01T08:00:00").unwrap(), "null");
+        assert!(t.age_at("Emma", "yesterday").is_err());
+    }
+
+    // --- Lifetime totals ---
+
+    #[test]
+    fn lifetime_totals_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-01-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(80.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-16T08:00:00").unwrap();
+        let totals: serde_json::Value = serde_json::from_str(&t.lifetime_totals(None)).unwrap();
+        assert_eq!(totals["total_feedings"], 2);
+        assert_eq!(totals["total_ml"], 200.0);
+        assert_eq!(totals["total_poop"], 1);
+        assert_eq!(totals["weigh_ins"], 0);
+    }
+
+    // --- Report ---
+
+    #[test]
+    fn report_returns_per_day_data() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-14T08:00:00").unwrap();
+        t.add_feeding("Emma", "bl", None, None, Some(15), None, None, None, "2026-02-15T10:00:00").unwrap();
+
+        let r = t.report(None, "2026-02-14", "2026-02-16").unwrap();
+        let days: Vec<serde_json::Value> = serde_json::from_str(&r).unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0]["date"], "2026-02-14");
+        assert_eq!(days[0]["total_feedings"], 1);
+        assert_eq!(days[0]["total_ml"], 120.0);
+        assert_eq!(days[1]["date"], "2026-02-15");
+        assert_eq!(days[1]["total_feedings"], 1);
+        assert_eq!(days[1]["total_minutes"], 15);
+    }
+
+    #[test]
+    fn daypart_report_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T19:30:00").unwrap();
+        let rows: Vec<serde_json::Value> = serde_json::from_str(&t.report_by_daypart(None, "2026-02-15", "2026-02-16").unwrap()).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["evening"]["feedings"], 1);
+        assert_eq!(rows[0]["evening"]["total_ml"], 120.0);
+        assert_eq!(rows[0]["morning"]["feedings"], 0);
+        assert!(t.report_by_daypart(None, "bad", "2026-02-16").is_err());
+    }
+
+    // --- Average daily volume ---
+
+    #[test]
+    fn avg_daily_ml_over_range() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-14T08:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(t.avg_daily_ml(None, "2026-02-14", "2026-02-16").unwrap(), 120.0);
+        assert!(t.avg_daily_ml(None, "bad", "2026-02-16").is_err());
+    }
+
+    // --- Calorie estimate ---
+
+    #[test]
+    fn estimated_calories_uses_configured_factors() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(100.0), None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "solid", None, None, None, None, None, None, "2026-02-15T12:00:00").unwrap();
+        t.set_calorie_factors(0.5, 40.0);
+        assert_eq!(t.estimated_calories(None, "2026-02-15", "2026-02-16").unwrap(), 90.0);
+    }
+
+    // --- Hourly histogram ---
+
+    #[test]
+    fn feeding_hour_histogram_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:45:00").unwrap();
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T13:10:00").unwrap();
+
+        let h: Vec<u64> = serde_json::from_str(&t.feeding_hour_histogram(None, "2026-02-15", "2026-02-16").unwrap()).unwrap();
+        assert_eq!(h.len(), 24);
+        assert_eq!(h[8], 1);
+        assert_eq!(h[13], 1);
+    }
+
+    #[test]
+    fn typical_day_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bottle", Some(120.0), None, None, None, None, None, "2026-02-15T08:45:00").unwrap();
+        let day: Vec<(u32, f64)> = serde_json::from_str(&t.typical_day(None, "2026-02-15", "2026-02-17").unwrap()).unwrap();
+        assert_eq!(day.len(), 24);
+        assert_eq!(day[8], (8, 60.0));
+    }
+
+    #[test]
+    fn nursing_duration_buckets_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bl", None, None, Some(12), None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(
+            t.nursing_duration_buckets(None, "2026-02-15", "2026-02-16").unwrap(),
+            r#"[["0-5",0],["5-10",0],["10-20",1],["20+",0]]"#
+        );
+        assert!(t.nursing_duration_buckets(None, "bad", "2026-02-16").is_err());
+    }
+
+    // --- Next feeding prediction ---
+
+    #[test]
+    fn predict_next_feeding_formats_timestamp() {
+        let mut t = Tracker::new();
+        assert_eq!(t.predict_next_feeding(None, "2026-02-15T12:00:00").unwrap(), None);
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", None, None, None, None, None, None, "2026-02-15T11:00:00").unwrap();
+        assert_eq!(
+            t.predict_next_feeding(None, "2026-02-15T12:00:00").unwrap(),
+            Some("2026-02-15T14:00:00".to_string())
+        );
+    }
+
+    // --- Cluster feeding ---
+
+    #[test]
+    fn cluster_windows_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bl", None, None, None, None, None, None, "2026-02-15T17:00:00").unwrap();
+        t.add_feeding("Emma", "br", None, None, None, None, None, None, "2026-02-15T17:30:00").unwrap();
+        t.add_feeding("Emma", "bl", None, None, None, None, None, None, "2026-02-15T18:00:00").unwrap();
+
+        let clusters: serde_json::Value = serde_json::from_str(&t.cluster_windows(None, 3, 30)).unwrap();
+        assert_eq!(clusters, serde_json::json!([["2026-02-15T17:00:00", "2026-02-15T18:00:00", 3]]));
+    }
+
+    #[test]
+    fn merged_nursing_sessions_json() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bl", None, None, Some(10), None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "br", None, None, Some(8), None, None, None, "2026-02-15T08:12:00").unwrap();
+        let sessions: serde_json::Value =
+            serde_json::from_str(&t.merged_nursing_sessions(None, "2026-02-15", "2026-02-16", 5).unwrap()).unwrap();
+        assert_eq!(sessions, serde_json::json!([["2026-02-15T08:00:00", 18]]));
+        assert!(t.merged_nursing_sessions(None, "bad", "2026-02-16", 5).is_err());
+    }
+
+    // --- Diaper gaps ---
+
+    #[test]
+    fn diaper_gaps_json() {
+        let mut t = Tracker::new();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T08:00:00").unwrap();
+        t.add_dejection("Emma", "poop", false, None, "2026-02-15T10:15:00").unwrap();
+        t.add_dejection("Emma", "urine", false, None, "2026-02-15T11:00:00").unwrap();
+        assert_eq!(t.diaper_gaps(None, "2026-02-15", "2026-02-16").unwrap(), "[135,45]");
+    }
+
+    // --- Breast balance ---
+
+    #[test]
+    fn breast_balance_returns_json_object() {
+        let mut t = Tracker::new();
+        t.add_feeding("Emma", "bl", None, None, Some(15), None, None, None, "2026-02-15T08:00:00").unwrap();
+        t.add_feeding("Emma", "br", None, None, Some(10), None, None, None, "2026-02-15T11:00:00").unwrap();
+        t.add_feeding("Emma", "bottle", Some(90.0), None, None, None, None, None, "2026-02-15T14:00:00").unwrap();
+
+        let b: serde_json::Value = serde_json::from_str(&t.breast_balance(None, "2026-02-15", "2026-02-16").unwrap()).unwrap();
+        assert_eq!(b["left_minutes"], 15);
+        assert_eq!(b["right_minutes"], 10);
+    }
+
+    #[test]
+    fn next_breast_uses_wire_names() {
+        let mut t = Tracker::new();
+        assert_eq!(t.next_breast(None), None);
+        t.add_feeding("Emma", "br", None, None, Some(10), None, None, None, "2026-02-15T08:00:00").unwrap();
+        assert_eq!(t.next_breast(None), Some("breast-left".to_string()));
+    }
+
+    #[test]
+    fn parse_various_formats() {
+        assert!(parse_timestamp("2026-02-15T08:00:00").is_ok());
+        assert!(parse_timestamp("2026-02-15T08:00").is_ok());
+        assert!(parse_timestamp("2026-02-15 08:00:00").is_ok());
+        assert!(parse_timestamp("2026-02-15 08:00").is_ok());
+        assert!(parse_timestamp("bad").is_err());
+    }
+
+    #[test]
+    fn strict_timestamps_reject_short_forms() {
+        assert!(parse_timestamp_strict("2026-02-15T08:00:00").is_ok());
+        for short in ["2026-02-15T08:00", "2026-02-15 08:00:00", "2026-02-15T08:00:00Z", "now"] {
+            assert!(parse_timestamp_strict(short).is_err(), "{}", short);
+        }
+
+        let mut t = Tracker::new();
+        assert!(t.add_weight("Emma", 3.5, None, "2026-02-15T08:00").is_ok());
+        t.set_strict_timestamps(true);
+        let err = t.add_weight("Emma", 3.5, None, "2026-02-15T09:00").unwrap_err();
+        assert_eq!(err, TrackerError::InvalidTimestamp("2026-02-15T09:00".to_string()));
+        let relative = r#"{"baby_name":"Emma","dejection_type":"urine","timestamp":"now","now":"2026-02-15T09:00:00"}"#;
+        assert!(t.add_entry("dejection", relative).is_err());
+        assert!(t.add_weight("Emma", 3.5, None, "2026-02-15T09:00:00").is_ok());
+
+        let mut t = Tracker::from_json(&t.export_data()).unwrap();
+        assert!(t.update_weight(1, 3.6, None, "2026-02-15T08:00").is_err());
+        assert!(t.duplicate_weight(1, Some("2026-02-16 08:00:00")).is_err());
+        assert!(t.update_weight(1, 3.6, None, "2026-02-15T08:00:00").unwrap());
+    }
+
+    #[test]
+    fn parse_relative_timestamps() {
+        let now = parse_timestamp("2026-02-15T03:00:00").unwrap();
+        assert_eq!(parse_timestamp_rel("now", now).unwrap(), now);
+        assert_eq!(parse_timestamp_rel("now-90m", now).unwrap(), parse_timestamp("2026-02-15T01:30:00").unwrap());
+        assert_eq!(parse_timestamp_rel("NOW-2h", now).unwrap(), parse_timestamp("2026-02-15T01:00:00").unwrap());
+        assert_eq!(parse_timestamp_rel("now+15m", now).unwrap(), parse_timestamp("2026-02-15T03:15:00").unwrap());
+        assert_eq!(parse_timestamp_rel("2026-02-14T20:00", now).unwrap(), parse_timestamp("2026-02-14T20:00:00").unwrap());
+        for bad in ["now-", "now-m", "now-30s", "now*2h", "now-1.5h", "now-3é", "nowé", "now--5m", "now-4000000000h", "later"] {
+            assert!(parse_timestamp_rel(bad, now).is_err(), "{}", bad);
+        }
+        assert!(parse_timestamp("now").is_err());
+    }
+
+    #[test]
+    fn add_entry_resolves_relative_time() {
+        let mut t = Tracker::new();
+        let now = r#""now":"2026-02-15T03:00:00""#;
+        t.add_entry("feeding", &format!(r#"{{"baby_name":"Emma","feeding_type":"bl","timestamp":"now-90m",{}}}"#, now)).unwrap();
+        t.add_entry("dejection", &format!(r#"{{"baby_name":"Emma","dejection_type":"urine","timestamp":"now",{}}}"#, now)).unwrap();
+        t.add_entry("weight", &format!(r#"{{"baby_name":"Emma","weight_kg":3.5,"timestamp":"now-2h",{}}}"#, now)).unwrap();
+
+        let ts_of = |id| serde_json::from_str::<serde_json::Value>(&t.get_entry(id)).unwrap()["timestamp"].clone();
+        assert_eq!(ts_of(1), "2026-02-15T01:30:00");
+        assert_eq!(ts_of(2), "2026-02-15T03:00:00");
+        assert_eq!(ts_of(3), "2026-02-15T01:00:00");
+        assert!(t.add_weight("Emma", 3.5, None, "now-2h").is_err());
+        assert!(t.add_entry("weight", r#"{"baby_name":"Emma","weight_kg":3.5,"timestamp":"now-2h"}"#).is_err());
+    }
+
+    #[test]
+    fn parse_rfc3339_keeps_local_time() {
+        let expected = parse_timestamp("2026-02-15T08:00:00").unwrap();
+        assert_eq!(parse_timestamp("2026-02-15T08:00:00+00:00").unwrap(), expected);
+        assert_eq!(parse_timestamp("2026-02-15T08:00:00Z").unwrap(), expected);
+        assert_eq!(parse_timestamp("2026-02-15T08:00:00+02:00").unwrap(), expected);
+        assert_eq!(parse_timestamp("2026-02-15T08:00:00.250-05:00").unwrap().format("%H:%M:%S%.3f").to_string(), "08:00:00.250");
+        assert!(parse_timestamp("2026-02-15T08:00:00+25:00").is_err());
     }
 }
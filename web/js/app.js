@@ -19,6 +19,7 @@ let currentMetric = 'total_feedings';
 // --- Persistence ---
 
 function save() {
+  if (!tracker.isDirty()) return;
   localStorage.setItem(STORAGE_KEY, tracker.exportData());
 }
 
@@ -26,6 +27,8 @@ function load() {
   const data = localStorage.getItem(STORAGE_KEY);
   if (data) {
     try { tracker = BabyTracker.loadData(data); } catch { tracker = new BabyTracker(); }
+    const warning = tracker.loadWarning();
+    if (warning) console.warn('Saved data failed its integrity check:', warning);
   } else {
     tracker = new BabyTracker();
   }
@@ -228,7 +231,7 @@ function renderTimeline() {
 
   $timeline.innerHTML = entries.map(e => {
     const icon = ICONS[e.subtype] || '';
-    const label = LABELS[e.subtype] || e.subtype;
+    const label = e.label || LABELS[e.subtype] || e.subtype;
     const meta = [];
     if (e.weight_kg != null) meta.push(`${e.weight_kg} kg`);
     if (e.amount_ml != null) meta.push(`${e.amount_ml} ml`);
@@ -337,7 +340,7 @@ function stopFeeding() {
   const timestamp = toISOTimestamp(startDate);
 
   try {
-    tracker.addFeeding(name, activeFeeding.type, undefined, durationMin, undefined, timestamp);
+    tracker.addFeeding(name, activeFeeding.type, undefined, undefined, durationMin, undefined, undefined, undefined, timestamp);
     save();
   } catch (err) {
     console.error('Failed to save feeding:', err);
@@ -382,7 +385,7 @@ function setupTypePicker() {
         const name = getBabyName();
         const timestamp = toISOTimestamp(new Date());
         try {
-          tracker.addDejection(name, type, undefined, timestamp);
+          tracker.addDejection(name, type, false, undefined, timestamp);
           save();
           currentDate = new Date();
           resetToStartOfDay(currentDate);
@@ -461,7 +464,7 @@ function setupSliderModal() {
     try {
       if (sliderMode === 'bottle') {
         const ml = parseFloat($sliderInput.value);
-        tracker.addFeeding(name, 'bottle', ml, undefined, notes, timestamp);
+        tracker.addFeeding(name, 'bottle', ml, undefined, undefined, notes, undefined, undefined, timestamp);
       } else {
         const kg = parseFloat($sliderInput.value);
         tracker.addWeight(name, kg, notes, timestamp);
@@ -580,6 +583,12 @@ function buildDejectionForm(entry) {
       <label for="edit-subtype">Type</label>
       <select id="edit-subtype">${options}</select>
     </div>
+    <div class="form-group form-check">
+      <label for="edit-rash">
+        <input type="checkbox" id="edit-rash" ${entry.rash ? 'checked' : ''}>
+        Rash
+      </label>
+    </div>
   `;
 }
 
@@ -600,7 +609,7 @@ function buildWeightForm(entry) {
 function handleEditSave(entry) {
   const notes = $editSheet.querySelector('#edit-notes').value.trim() || undefined;
   const timestamp = $editSheet.querySelector('#edit-time').value + ':00';
-  const id = entry.id;
+  const id = BigInt(entry.id);
 
   try {
     if (entry.kind === 'feeding') {
@@ -609,10 +618,11 @@ function handleEditSave(entry) {
       const durEl = $editSheet.querySelector('#edit-duration');
       const amount = amountEl.value ? parseFloat(amountEl.value) : undefined;
       const duration = durEl.value ? parseInt(durEl.value) : undefined;
-      tracker.updateFeeding(id, subtype, amount, duration, notes, timestamp);
+      tracker.updateFeeding(id, subtype, amount, entry.leftover_ml, duration, notes, entry.source, entry.fussiness, timestamp);
     } else if (entry.kind === 'dejection') {
       const subtype = $editSheet.querySelector('#edit-subtype').value;
-      tracker.updateDejection(id, subtype, notes, timestamp);
+      const rash = $editSheet.querySelector('#edit-rash').checked;
+      tracker.updateDejection(id, subtype, rash, notes, timestamp);
     } else if (entry.kind === 'weight') {
       const kg = parseFloat($editSheet.querySelector('#edit-weight-kg').value);
       tracker.updateWeight(id, kg, notes, timestamp);
@@ -630,11 +640,11 @@ function handleEditSave(entry) {
 function handleEditDelete(entry) {
   try {
     if (entry.kind === 'feeding') {
-      tracker.deleteFeeding(entry.id);
+      tracker.deleteFeeding(BigInt(entry.id));
     } else if (entry.kind === 'dejection') {
-      tracker.deleteDejection(entry.id);
+      tracker.deleteDejection(BigInt(entry.id));
     } else if (entry.kind === 'weight') {
-      tracker.deleteWeight(entry.id);
+      tracker.deleteWeight(BigInt(entry.id));
     }
     save();
     hideEditModal();